@@ -1,10 +1,14 @@
-use kewb::{error::Error, fs::write_table};
+use kewb::{
+    error::Error,
+    fs::{is_current_table, write_table},
+};
 use std::fs::read;
 
 fn main() -> Result<(), Error> {
     let table = read("bin/table.bin")?;
 
-    if table.is_empty() {
+    // tables written by another version of kewb are generated again
+    if !is_current_table(&table) {
         write_table("bin/table.bin")?;
     }
 
@@ -1,4 +1,4 @@
-use clap::{arg, command, Parser, Subcommand, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use crossterm::{
     cursor::{MoveLeft, MoveRight, MoveUp},
     execute,
@@ -6,8 +6,9 @@ use crossterm::{
 };
 use kewb::{
     error::Error,
-    fs::{decode_table, write_table},
+    fs::{decode_table, write_symmetry_table, write_table},
     generators::*,
+    metrics::Metric,
     scramble::{scramble_from_state, scramble_from_str},
     Color,
 };
@@ -15,6 +16,7 @@ use kewb::{CubieCube, FaceCube, Solver};
 use spinners::Spinner;
 use std::{
     io::{self, stdout},
+    ops::ControlFlow,
     process,
     time::Instant,
 };
@@ -52,6 +54,9 @@ enum Commands {
 
         #[arg(short, long)]
         details: bool,
+
+        #[arg(long, default_value = "htm")]
+        metric: MoveMetric,
     },
 
     #[command(about = "generates scramble")]
@@ -67,7 +72,31 @@ enum Commands {
     },
 
     #[command(about = "generates the table used by the solver")]
-    Table { path: String },
+    Table {
+        path: String,
+
+        #[arg(short, long, help = "includes the symmetry reduced pruning tables")]
+        symmetry: bool,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+enum MoveMetric {
+    Htm,
+    Qtm,
+    Stm,
+    Etm,
+}
+
+impl From<MoveMetric> for Metric {
+    fn from(value: MoveMetric) -> Self {
+        match value {
+            MoveMetric::Htm => Metric::Htm,
+            MoveMetric::Qtm => Metric::Qtm,
+            MoveMetric::Stm => Metric::Stm,
+            MoveMetric::Etm => Metric::Etm,
+        }
+    }
 }
 
 #[derive(ValueEnum, Clone)]
@@ -87,11 +116,12 @@ fn solve(
     max: u8,
     timeout: Option<f32>,
     details: bool,
+    metric: Metric,
 ) -> Result<(), Error> {
     if let Some(scramble) = scramble {
-        solve_scramble(scramble, max, timeout, details)?;
+        solve_scramble(scramble, max, timeout, details, metric)?;
     } else if let Some(facelet) = facelet {
-        solve_facelet(facelet, max, timeout, details)?;
+        solve_facelet(facelet, max, timeout, details, metric)?;
     }
 
     Ok(())
@@ -102,16 +132,31 @@ fn solve_state(
     max: u8,
     timeout: Option<f32>,
     details: bool,
+    metric: Metric,
 ) -> Result<(), Error> {
     let table = decode_table(TABLE)?;
     let mut solver = Solver::new(&table, max, timeout);
-    let mut spinner = Spinner::new(spinners::Spinners::Dots, "Solving".to_owned());
-
     let start = Instant::now();
-    let solution = solver.solve(state);
-    let end = Instant::now();
 
-    spinner.stop_with_newline();
+    // with a timeout, every improvement is printed as soon as it is found
+    let solution = if timeout.is_some() {
+        solver.solve_with(state, |event| {
+            println!(
+                "Found {} moves after {:.3}s (depth {}): {}",
+                event.solution.move_count(metric),
+                event.elapsed.as_secs_f32(),
+                event.depth,
+                event.solution
+            );
+            ControlFlow::Continue(())
+        })
+    } else {
+        let mut spinner = Spinner::new(spinners::Spinners::Dots, "Solving".to_owned());
+        let solution = solver.solve(state);
+        spinner.stop_with_newline();
+        solution
+    };
+    let end = Instant::now();
 
     match timeout {
         Some(timeout) => println!("Timeout: {:.2}s", timeout),
@@ -126,7 +171,7 @@ fn solve_state(
             }
 
             println!("Solution: {value}");
-            println!("Move count: {}", value.len())
+            println!("Move count: {} ({metric})", value.move_count(metric))
         }
         None => println!("No solution found"),
     }
@@ -139,19 +184,33 @@ fn solve_scramble(
     max: u8,
     timeout: Option<f32>,
     details: bool,
+    metric: Metric,
 ) -> Result<(), Error> {
     let scramble = scramble_from_str(scramble)?;
     let state = CubieCube::from(&scramble);
 
-    solve_state(state, max, timeout, details)
+    solve_state(state, max, timeout, details, metric)
 }
 
-fn solve_facelet(facelet: &str, max: u8, timeout: Option<f32>, details: bool) -> Result<(), Error> {
-    if let Ok(face_cube) = FaceCube::try_from(facelet) {
-        match CubieCube::try_from(&face_cube) {
-            Ok(state) => Ok(solve_state(state, max, timeout, details)?),
-            Err(_) => Err(Error::InvalidFaceletValue),
+fn solve_facelet(
+    facelet: &str,
+    max: u8,
+    timeout: Option<f32>,
+    details: bool,
+    metric: Metric,
+) -> Result<(), Error> {
+    // strings using colors instead of face names get their faces from the centers
+    let face_cube = FaceCube::try_from(facelet).or_else(|_| FaceCube::from_colors(facelet));
+
+    if let Ok(face_cube) = face_cube {
+        let report = face_cube.validate();
+
+        if !report.is_valid() {
+            eprintln!("{report}");
         }
+
+        let state = CubieCube::try_from(&face_cube)?;
+        solve_state(state, max, timeout, details, metric)
     } else {
         Err(Error::InvalidFaceletString)
     }
@@ -264,11 +323,15 @@ fn scramble(state: &State, number: usize, preview: bool) -> Result<(), Error> {
     Ok(())
 }
 
-fn table(path: &str) -> Result<(), Error> {
+fn table(path: &str, symmetry: bool) -> Result<(), Error> {
     let mut spinner = Spinner::new(spinners::Spinners::Dots, "Generating scramble".to_owned());
     let start = Instant::now();
 
-    write_table(path)?;
+    if symmetry {
+        write_symmetry_table(path)?;
+    } else {
+        write_table(path)?;
+    }
 
     let end = Instant::now();
     spinner.stop_with_newline();
@@ -288,13 +351,21 @@ fn main() {
             max,
             timeout,
             details,
-        }) => solve(scramble, facelet, *max, *timeout, *details),
+            metric,
+        }) => solve(
+            scramble,
+            facelet,
+            *max,
+            *timeout,
+            *details,
+            (*metric).into(),
+        ),
         Some(Commands::Scramble {
             state,
             number,
             preview,
         }) => scramble(state, *number, *preview),
-        Some(Commands::Table { path }) => table(path),
+        Some(Commands::Table { path, symmetry }) => table(path, *symmetry),
         _ => Ok(()),
     };
 
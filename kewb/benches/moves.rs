@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kewb::{packed::PackedCube, scramble::scramble_from_str, CubieCube, Move, MOVE_CUBES};
+
+/// Builds the move cube from its quarter turn on every call, as `apply_move` did before `MOVE_CUBES`.
+fn rebuilt_move(m: Move) -> CubieCube {
+    let quarter = MOVE_CUBES[m as usize / 3 * 3];
+
+    (1..m.get_power()).fold(quarter, |acc, _| acc * quarter)
+}
+
+fn bench_moves(c: &mut Criterion) {
+    let moves =
+        scramble_from_str("R U R' U' F2 D L' B2 U2 R2 F' D2 L B' U R2 D' F L2 B U'").unwrap();
+    let state = CubieCube::default();
+    let packed = PackedCube::default();
+
+    c.bench_function("rebuilt_apply_move", |b| {
+        b.iter(|| {
+            moves
+                .iter()
+                .fold(black_box(state), |acc, m| acc * rebuilt_move(*m))
+        })
+    });
+
+    c.bench_function("apply_move", |b| {
+        b.iter(|| black_box(state).apply_moves(&moves))
+    });
+
+    c.bench_function("packed_apply_move", |b| {
+        b.iter(|| black_box(packed).apply_moves(&moves))
+    });
+}
+
+criterion_group!(benches, bench_moves);
+criterion_main!(benches);
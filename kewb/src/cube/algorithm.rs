@@ -0,0 +1,405 @@
+use std::{fmt, str::FromStr};
+
+use crate::error::Error;
+
+use super::{
+    moves::Move,
+    notation::{expand, ExtMove},
+    transform::invert,
+};
+
+/// Maximum number of moves of an expanded algorithm, larger repetitions are rejected.
+pub const MAX_EXPANDED_MOVES: usize = 100_000;
+
+/// Maximum nesting of groups, commutators and conjugates, deeper algorithms are rejected.
+pub const MAX_NESTING: usize = 64;
+
+/// Node of a parsed algorithm.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AlgNode {
+    /// A single move: R, Uw2, M', x.
+    Move(ExtMove),
+    /// Parenthesized sequence: (R U R' U').
+    Group(Vec<AlgNode>),
+    /// Commutator, expands to A B A' B': [R U R', D].
+    Commutator(Vec<AlgNode>, Vec<AlgNode>),
+    /// Conjugate, expands to A B A': [F: R U R' U'].
+    Conjugate(Vec<AlgNode>, Vec<AlgNode>),
+    /// Node repeated n times, inverted when n is negative: (R U R' U')3, [R, U]'.
+    Repeat(Box<AlgNode>, i32),
+}
+
+/// Algorithm parsed from the usual notation, supports grouping with repetition,
+/// commutators, conjugates, `//` line comments and `/* */` block comments.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Algorithm {
+    pub nodes: Vec<AlgNode>,
+}
+
+fn nodes_len(nodes: &[AlgNode]) -> usize {
+    nodes
+        .iter()
+        .fold(0, |sum, node| sum.saturating_add(node.len()))
+}
+
+fn expand_nodes(nodes: &[AlgNode]) -> Vec<ExtMove> {
+    nodes.iter().flat_map(|node| node.expand()).collect()
+}
+
+impl AlgNode {
+    /// Returns the number of moves of the expanded node, saturating on overflow.
+    pub fn len(&self) -> usize {
+        match self {
+            AlgNode::Move(_) => 1,
+            AlgNode::Group(nodes) => nodes_len(nodes),
+            AlgNode::Commutator(a, b) => {
+                nodes_len(a).saturating_add(nodes_len(b)).saturating_mul(2)
+            }
+            AlgNode::Conjugate(a, b) => nodes_len(a).saturating_mul(2).saturating_add(nodes_len(b)),
+            AlgNode::Repeat(node, count) => {
+                node.len().saturating_mul(count.unsigned_abs() as usize)
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Expands the node into a flat sequence of moves.
+    pub fn expand(&self) -> Vec<ExtMove> {
+        match self {
+            AlgNode::Move(m) => vec![*m],
+            AlgNode::Group(nodes) => expand_nodes(nodes),
+            AlgNode::Commutator(a, b) => {
+                let (a, b) = (expand_nodes(a), expand_nodes(b));
+                [a.clone(), b.clone(), invert(&a), invert(&b)].concat()
+            }
+            AlgNode::Conjugate(a, b) => {
+                let (a, b) = (expand_nodes(a), expand_nodes(b));
+                [a.clone(), b, invert(&a)].concat()
+            }
+            AlgNode::Repeat(node, count) => {
+                let moves = if *count < 0 {
+                    invert(&node.expand())
+                } else {
+                    node.expand()
+                };
+                moves.repeat(count.unsigned_abs() as usize)
+            }
+        }
+    }
+}
+
+impl Algorithm {
+    /// Returns the number of moves of the expanded algorithm, saturating on overflow.
+    pub fn len(&self) -> usize {
+        nodes_len(&self.nodes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Expands the algorithm into a flat sequence of extended moves.
+    pub fn expand(&self) -> Vec<ExtMove> {
+        expand_nodes(&self.nodes)
+    }
+
+    /// Expands the algorithm into face turns.
+    pub fn to_moves(&self) -> Vec<Move> {
+        expand(&self.expand())
+    }
+}
+
+fn join_nodes(nodes: &[AlgNode]) -> String {
+    nodes
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl fmt::Display for AlgNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlgNode::Move(m) => write!(f, "{m}"),
+            AlgNode::Group(nodes) => write!(f, "({})", join_nodes(nodes)),
+            AlgNode::Commutator(a, b) => write!(f, "[{}, {}]", join_nodes(a), join_nodes(b)),
+            AlgNode::Conjugate(a, b) => write!(f, "[{}: {}]", join_nodes(a), join_nodes(b)),
+            AlgNode::Repeat(node, count) => match count {
+                -1 => write!(f, "{node}'"),
+                c if *c < 0 => write!(f, "{node}{}'", -c),
+                c => write!(f, "{node}{c}"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", join_nodes(&self.nodes))
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn error(&self, offset: usize, token: &str) -> Error {
+        Error::InvalidAlgorithm {
+            offset,
+            token: token.to_owned(),
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), Error> {
+        loop {
+            let rest = &self.input[self.pos..];
+
+            if rest.starts_with("//") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if rest.starts_with("/*") {
+                match rest.find("*/") {
+                    Some(end) => self.pos += end + 2,
+                    None => return Err(self.error(self.pos, "/*")),
+                }
+            } else if self.peek().is_some_and(char::is_whitespace) {
+                self.bump();
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parses a nested sequence opened by the token at `start`.
+    fn parse_nested(
+        &mut self,
+        terminators: &[char],
+        start: usize,
+        token: &str,
+    ) -> Result<Vec<AlgNode>, Error> {
+        if self.depth == MAX_NESTING {
+            return Err(self.error(start, token));
+        }
+
+        self.depth += 1;
+        let nodes = self.parse_sequence(terminators);
+        self.depth -= 1;
+
+        nodes
+    }
+
+    fn parse_sequence(&mut self, terminators: &[char]) -> Result<Vec<AlgNode>, Error> {
+        let mut nodes = Vec::new();
+
+        loop {
+            self.skip_trivia()?;
+
+            let start = self.pos;
+            let node = match self.peek() {
+                None => break,
+                Some(c) if terminators.contains(&c) => break,
+                Some('(') => {
+                    self.bump();
+                    let nodes = self.parse_nested(&[')'], start, "(")?;
+                    self.expect(&[')'], start, "(")?;
+                    AlgNode::Group(nodes)
+                }
+                Some('[') => {
+                    self.bump();
+                    let a = self.parse_nested(&[',', ':'], start, "[")?;
+                    let separator = self.expect(&[',', ':'], start, "[")?;
+                    let b = self.parse_nested(&[']'], start, "[")?;
+                    self.expect(&[']'], start, "[")?;
+
+                    match separator {
+                        ',' => AlgNode::Commutator(a, b),
+                        _ => AlgNode::Conjugate(a, b),
+                    }
+                }
+                Some(c) if c.is_ascii_alphabetic() => self.parse_move()?,
+                Some(c) => return Err(self.error(start, &c.to_string())),
+            };
+
+            nodes.push(self.parse_suffix(node)?);
+        }
+
+        Ok(nodes)
+    }
+
+    /// Consumes one of the expected closing characters, otherwise reports the opening token.
+    fn expect(&mut self, expected: &[char], start: usize, opening: &str) -> Result<char, Error> {
+        match self.peek() {
+            Some(c) if expected.contains(&c) => {
+                self.bump();
+                Ok(c)
+            }
+            _ => Err(self.error(start, opening)),
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    fn parse_move(&mut self) -> Result<AlgNode, Error> {
+        let start = self.pos;
+
+        self.bump();
+        if self.peek() == Some('w') {
+            self.bump();
+        }
+        self.take_while(|c| c.is_ascii_digit());
+        if self.peek() == Some('\'') {
+            self.bump();
+        }
+
+        let token = &self.input[start..self.pos];
+
+        ExtMove::from_str(token)
+            .map(AlgNode::Move)
+            .map_err(|_| self.error(start, token))
+    }
+
+    fn parse_suffix(&mut self, node: AlgNode) -> Result<AlgNode, Error> {
+        if matches!(node, AlgNode::Move(_)) {
+            return Ok(node);
+        }
+
+        let start = self.pos;
+        self.take_while(|c| c.is_ascii_digit());
+        let digits = &self.input[start..self.pos];
+        let inverse = self.peek() == Some('\'');
+
+        if inverse {
+            self.bump();
+        }
+
+        let count = match digits {
+            "" => 1,
+            _ => digits
+                .parse::<i32>()
+                .map_err(|_| self.error(start, digits))?,
+        };
+
+        let node = match (count, inverse) {
+            (1, false) if digits.is_empty() => node,
+            (count, true) => AlgNode::Repeat(Box::new(node), -count),
+            (count, false) => AlgNode::Repeat(Box::new(node), count),
+        };
+
+        if node.len() > MAX_EXPANDED_MOVES {
+            return Err(self.error(start, digits));
+        }
+
+        Ok(node)
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            input: s,
+            pos: 0,
+            depth: 0,
+        };
+        let nodes = parser.parse_sequence(&[])?;
+        let algorithm = Algorithm { nodes };
+
+        if algorithm.len() > MAX_EXPANDED_MOVES {
+            return Err(parser.error(0, s));
+        }
+
+        Ok(algorithm)
+    }
+}
+
+/// Parses an algorithm and expands it into face turns.
+pub fn algorithm_from_str(s: &str) -> Result<Vec<Move>, Error> {
+    Ok(Algorithm::from_str(s)?.to_moves())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Move::*;
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            algorithm_from_str("(R U R' U')3").unwrap(),
+            [R, U, R3, U3].repeat(3)
+        );
+        assert_eq!(
+            algorithm_from_str("[R U R', D]").unwrap(),
+            vec![R, U, R3, D, R, U3, R3, D3]
+        );
+        assert_eq!(
+            algorithm_from_str("[F: [R, U]]").unwrap(),
+            vec![F, R, U, R3, U3, F3]
+        );
+        assert_eq!(algorithm_from_str("(R U)2'").unwrap(), vec![U3, R3, U3, R3]);
+        assert_eq!(algorithm_from_str("[R, U]'").unwrap(), vec![U, R, U3, R3]);
+        assert_eq!(algorithm_from_str("RUR'U'").unwrap(), vec![R, U, R3, U3]);
+        assert_eq!(algorithm_from_str("(R U)0 F").unwrap(), vec![F]);
+    }
+
+    #[test]
+    fn test_comments() {
+        let alg = "R U R' // insert pair\n/* setup */ U' (R U)2";
+        assert_eq!(
+            algorithm_from_str(alg).unwrap(),
+            vec![R, U, R3, U3, R, U, R, U]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let alg = Algorithm::from_str("[F: [R, U]]2 (R U R' U')' M2 // comment").unwrap();
+        assert_eq!(alg.to_string(), "[F: [R, U]]2 (R U R' U')' M2");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |s: &str| match Algorithm::from_str(s) {
+            Err(Error::InvalidAlgorithm { offset, token }) => (offset, token),
+            _ => panic!("expected an algorithm error for {s}"),
+        };
+
+        assert_eq!(error("R U Q2 F"), (4, "Q2".to_owned()));
+        assert_eq!(error("R U3"), (2, "U3".to_owned()));
+        assert_eq!(error("(R U"), (0, "(".to_owned()));
+        assert_eq!(error("R [U, F"), (2, "[".to_owned()));
+        assert_eq!(error("R U)"), (3, ")".to_owned()));
+        assert_eq!(error("R /* U"), (2, "/*".to_owned()));
+        assert_eq!(error("(R U)2000000000"), (5, "2000000000".to_owned()));
+        assert_eq!(error("((R U)1000)1000"), (11, "1000".to_owned()));
+        assert!(Algorithm::from_str("(R U)50000").is_ok());
+
+        let nested = |depth: usize| format!("{}R{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(Algorithm::from_str(&nested(MAX_NESTING)).is_ok());
+        assert_eq!(error(&nested(200_000)), (MAX_NESTING, "(".to_owned()));
+        assert_eq!(
+            error(&"[R, ".repeat(100)),
+            (4 * MAX_NESTING, "[".to_owned())
+        );
+    }
+}
@@ -12,3 +12,5 @@ pub const EP_COUNT: u32 = 479001599;
 pub const UD_EP_COUNT: u16 = 40320;
 /// The total count of E-slice edge permutations.
 pub const E_EP_COUNT: u16 = 24;
+/// The total count of solvable cube states.
+pub const STATE_COUNT: u128 = 43_252_003_274_489_856_000;
@@ -1,14 +1,19 @@
-use self::{Corner::*, Edge::*, Move::*};
+use self::{Corner::*, Edge::*};
 use crate::error::Error;
 
-use super::{facelet::*, moves::*, utils::has_duplicates};
-use std::ops::Mul;
+use super::{facelet::*, index::state_to_index, moves::*, utils::has_duplicates};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Mul,
+};
 
 /// Represents the 8 corners on the cube, described by the layer they are on.
 /// Example: UBL (Up, Bottom, Left).
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Corner {
     UBL, UBR, UFR, UFL,
     DFL, DFR, DBR, DBL,
@@ -36,7 +41,8 @@ impl TryFrom<u8> for Corner {
 /// Example: BL (Bottom, Left).
 #[rustfmt::skip]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edge {
     BL, BR, FR, FL,
     UB, UR, UF, UL,
@@ -65,8 +71,68 @@ impl TryFrom<u8> for Edge {
     }
 }
 
+/// Cycle of pieces, listed by position: the piece at each position belongs to the next one.
+/// Pieces twisted or flipped in place are cycles of length 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cycle<T> {
+    pub positions: Vec<T>,
+    /// Total orientation of the pieces in the cycle, modulo 3 for corners and 2 for edges.
+    pub orientation: u8,
+}
+
+impl<T> Cycle<T> {
+    /// Returns the number of repetitions needed to solve the pieces of the cycle.
+    pub fn order(&self, orientation_cases: u8) -> usize {
+        match self.orientation {
+            0 => self.positions.len(),
+            _ => self.positions.len() * orientation_cases as usize,
+        }
+    }
+}
+
+/// Finds the cycles of a permutation, `pieces` names the positions.
+fn find_cycles<T: Copy>(perm: &[usize], ori: &[u8], pieces: &[T], cases: u8) -> Vec<Cycle<T>> {
+    let mut visited = vec![false; perm.len()];
+    let mut cycles = Vec::new();
+
+    for start in 0..perm.len() {
+        let mut positions = Vec::new();
+        let mut orientation = 0;
+        let mut i = start;
+
+        while !visited[i] {
+            visited[i] = true;
+            positions.push(pieces[i]);
+            orientation = (orientation + ori[i]) % cases;
+            i = perm[i];
+        }
+
+        if positions.len() > 1 || orientation != 0 {
+            cycles.push(Cycle {
+                positions,
+                orientation,
+            });
+        }
+    }
+
+    cycles
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
 /// Cube on the cubie level.
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// States are hashed and ordered by their rank, see `index::state_to_index`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::cube::serialization::CubieFields")
+)]
 pub struct CubieCube {
     /// Corner permutation, relative to SOLVED_STATE.
     pub cp: [Corner; 8],
@@ -92,52 +158,75 @@ impl Default for CubieCube {
     }
 }
 
-impl Mul for CubieCube {
-    type Output = Self;
+/// Composes two corner orientations.
+/// Values from 3 to 5 are used by mirrored corners, which only appear in the reflections of the cube.
+const fn multiply_corner_orientation(a: u8, b: u8) -> u8 {
+    match (a < 3, b < 3) {
+        (true, true) => (a + b) % 3,
+        (true, false) => 3 + (a + b) % 3,
+        (false, true) => 3 + (a + 3 - b) % 3,
+        (false, false) => (a + 3 - b) % 3,
+    }
+}
 
-    fn mul(self, rhs: CubieCube) -> Self::Output {
-        let mut res = CubieCube::default();
-        // (A * B).c = A(B(x).c).c
-        // (A * B).o = A(B(x).c).o + B(x).o
+/// Multiplies two states, usable in constants.
+pub(crate) const fn multiply(a: &CubieCube, b: &CubieCube) -> CubieCube {
+    let mut res = SOLVED_CUBIE_CUBE;
+    let mut i = 0;
+
+    // (A * B).c = A(B(x).c).c
+    // (A * B).o = A(B(x).c).o + B(x).o
+    while i < 8 {
+        res.cp[i] = a.cp[b.cp[i] as usize];
+        res.co[i] = multiply_corner_orientation(a.co[b.cp[i] as usize], b.co[i]);
+        i += 1;
+    }
 
-        for i in 0..8 {
-            res.cp[i] = self.cp[rhs.cp[i] as usize];
-            res.co[i] = (self.co[rhs.cp[i] as usize] + rhs.co[i]) % 3;
-        }
+    i = 0;
 
-        for i in 0..12 {
-            res.ep[i] = self.ep[rhs.ep[i] as usize];
-            res.eo[i] = (self.eo[rhs.ep[i] as usize] + rhs.eo[i]) % 2;
-        }
+    while i < 12 {
+        res.ep[i] = a.ep[b.ep[i] as usize];
+        res.eo[i] = (a.eo[b.ep[i] as usize] + b.eo[i]) % 2;
+        i += 1;
+    }
 
-        res
+    res
+}
+
+impl Hash for CubieCube {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state_to_index(self).hash(state);
+    }
+}
+
+impl Ord for CubieCube {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the rank only identifies solvable states, the raw values break the ties of the others
+        let raw = |s: &Self| (s.cp.map(|c| c as u8), s.co, s.ep.map(|e| e as u8), s.eo);
+
+        state_to_index(self)
+            .cmp(&state_to_index(other))
+            .then_with(|| raw(self).cmp(&raw(other)))
+    }
+}
+
+impl PartialOrd for CubieCube {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Mul for CubieCube {
+    type Output = Self;
+
+    fn mul(self, rhs: CubieCube) -> Self::Output {
+        multiply(&self, &rhs)
     }
 }
 
 impl CubieCube {
     pub fn apply_move(self, move_name: Move) -> Self {
-        let move_state = match move_name {
-            U => U_MOVE,
-            U2 => U_MOVE * U_MOVE,
-            U3 => U_MOVE * U_MOVE * U_MOVE,
-            D => D_MOVE,
-            D2 => D_MOVE * D_MOVE,
-            D3 => D_MOVE * D_MOVE * D_MOVE,
-            R => R_MOVE,
-            R2 => R_MOVE * R_MOVE,
-            R3 => R_MOVE * R_MOVE * R_MOVE,
-            L => L_MOVE,
-            L2 => L_MOVE * L_MOVE,
-            L3 => L_MOVE * L_MOVE * L_MOVE,
-            F => F_MOVE,
-            F2 => F_MOVE * F_MOVE,
-            F3 => F_MOVE * F_MOVE * F_MOVE,
-            B => B_MOVE,
-            B2 => B_MOVE * B_MOVE,
-            B3 => B_MOVE * B_MOVE * B_MOVE,
-        };
-
-        self * move_state
+        self * MOVE_CUBES[move_name as usize]
     }
 
     /// Applies the sequence of moves to the current state.
@@ -189,6 +278,122 @@ impl CubieCube {
         count
     }
 
+    /// Checks if every piece is in place and correctly oriented.
+    pub fn is_solved(&self) -> bool {
+        *self == SOLVED_CUBIE_CUBE
+    }
+
+    /// Returns the corner at the given position and its orientation.
+    pub fn corner_at(&self, position: Corner) -> (Corner, u8) {
+        (self.cp[position as usize], self.co[position as usize])
+    }
+
+    /// Returns the edge at the given position and its orientation.
+    pub fn edge_at(&self, position: Edge) -> (Edge, u8) {
+        (self.ep[position as usize], self.eo[position as usize])
+    }
+
+    /// Returns the position of the corner and its orientation, `None` if the corner is missing.
+    pub fn locate_corner(&self, corner: Corner) -> Option<(Corner, u8)> {
+        let i = self.cp.iter().position(|c| *c == corner)?;
+        Some((SOLVED_CUBIE_CUBE.cp[i], self.co[i]))
+    }
+
+    /// Returns the position of the edge and its orientation, `None` if the edge is missing.
+    pub fn locate_edge(&self, edge: Edge) -> Option<(Edge, u8)> {
+        let i = self.ep.iter().position(|e| *e == edge)?;
+        Some((SOLVED_CUBIE_CUBE.ep[i], self.eo[i]))
+    }
+
+    /// Checks if the corner is in its position and correctly oriented.
+    pub fn is_corner_solved(&self, corner: Corner) -> bool {
+        self.corner_at(corner) == (corner, 0)
+    }
+
+    /// Checks if the edge is in its position and correctly oriented.
+    pub fn is_edge_solved(&self, edge: Edge) -> bool {
+        self.edge_at(edge) == (edge, 0)
+    }
+
+    /// Returns the corners which are not in their position.
+    pub fn misplaced_corners(&self) -> impl Iterator<Item = Corner> + '_ {
+        SOLVED_CUBIE_CUBE
+            .cp
+            .into_iter()
+            .filter(|c| self.locate_corner(*c).is_some_and(|(p, _)| p != *c))
+    }
+
+    /// Returns the edges which are not in their position.
+    pub fn misplaced_edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        SOLVED_CUBIE_CUBE
+            .ep
+            .into_iter()
+            .filter(|e| self.locate_edge(*e).is_some_and(|(p, _)| p != *e))
+    }
+
+    /// Returns the corners which are twisted, wherever they are.
+    pub fn misoriented_corners(&self) -> impl Iterator<Item = Corner> + '_ {
+        SOLVED_CUBIE_CUBE
+            .cp
+            .into_iter()
+            .filter(|c| self.locate_corner(*c).is_some_and(|(_, o)| o != 0))
+    }
+
+    /// Returns the edges which are flipped, wherever they are.
+    pub fn misoriented_edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        SOLVED_CUBIE_CUBE
+            .ep
+            .into_iter()
+            .filter(|e| self.locate_edge(*e).is_some_and(|(_, o)| o != 0))
+    }
+
+    /// Returns the inverse state, applying it after the current state gives the solved cube.
+    pub fn inverse(&self) -> Self {
+        let mut res = CubieCube::default();
+
+        for i in 0..8 {
+            res.cp[self.cp[i] as usize] = SOLVED_CUBIE_CUBE.cp[i];
+        }
+
+        for i in 0..8 {
+            let co = self.co[res.cp[i] as usize];
+            // mirrored orientations are their own inverse
+            res.co[i] = if co >= 3 { co } else { (3 - co) % 3 };
+        }
+
+        for i in 0..12 {
+            res.ep[self.ep[i] as usize] = SOLVED_CUBIE_CUBE.ep[i];
+        }
+
+        for i in 0..12 {
+            res.eo[i] = self.eo[res.ep[i] as usize];
+        }
+
+        res
+    }
+
+    /// Returns the corner cycles, including the corners twisted in place.
+    pub fn corner_cycles(&self) -> Vec<Cycle<Corner>> {
+        let cp = self.cp.map(|c| c as usize);
+        find_cycles(&cp, &self.co, &SOLVED_CUBIE_CUBE.cp, 3)
+    }
+
+    /// Returns the edge cycles, including the edges flipped in place.
+    pub fn edge_cycles(&self) -> Vec<Cycle<Edge>> {
+        let ep = self.ep.map(|e| e as usize);
+        find_cycles(&ep, &self.eo, &SOLVED_CUBIE_CUBE.ep, 2)
+    }
+
+    /// Returns the number of repetitions of the state needed to get back to the solved cube.
+    pub fn order(&self) -> usize {
+        let corners = self.corner_cycles().into_iter().map(|c| c.order(3));
+        let edges = self.edge_cycles().into_iter().map(|c| c.order(2));
+
+        corners
+            .chain(edges)
+            .fold(1, |acc, order| acc / gcd(acc, order) * order)
+    }
+
     /// Checks if CubieCube is a valid cubie representation.
     pub fn is_solvable(&self) -> bool {
         if has_duplicates(&self.cp) || has_duplicates(&self.ep) {
@@ -200,7 +405,7 @@ impl CubieCube {
         let c_twist = self.count_corner_twist();
         let e_twist = self.count_edge_twist();
         let has_even_permutation = c_perm % 2 == e_perm % 2;
-        let has_valid_twist = c_twist % 3 == 0 && e_twist % 2 == 0;
+        let has_valid_twist = c_twist.is_multiple_of(3) && e_twist.is_multiple_of(2);
 
         has_even_permutation && has_valid_twist
     }
@@ -216,6 +421,8 @@ impl From<&Vec<Move>> for CubieCube {
 impl TryFrom<&FaceCube> for CubieCube {
     type Error = Error;
     fn try_from(face_cube: &FaceCube) -> Result<Self, Self::Error> {
+        face_cube.validate().into_result()?;
+
         let mut state = CubieCube::default();
         let mut ori: usize = 0;
         let mut col1;
@@ -270,17 +477,14 @@ impl TryFrom<&FaceCube> for CubieCube {
             }
         }
 
-        if !state.is_solvable() {
-            Err(Error::InvalidFaceletValue)
-        } else {
-            Ok(state)
-        }
+        Ok(state)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::cube::moves::Move::*;
 
     #[test]
     fn test_mult() {
@@ -352,6 +556,92 @@ mod test {
         assert_eq!(state.count_edge_perm(), 1);
     }
 
+    #[test]
+    fn test_inverse() {
+        let scramble = vec![
+            U, F3, D3, F2, D, B2, D3, R2, U3, F2, R2, D2, R2, U3, L, B, L, R, F3, D, B3,
+        ];
+        let inverse = scramble.iter().rev().map(|m| m.get_inverse()).collect();
+        let state = CubieCube::from(&scramble);
+
+        assert_eq!(state.inverse(), CubieCube::from(&inverse));
+        assert_eq!(state * state.inverse(), SOLVED_CUBIE_CUBE);
+        assert_eq!(state.inverse() * state, SOLVED_CUBIE_CUBE);
+    }
+
+    #[test]
+    fn test_order() {
+        assert_eq!(CubieCube::default().order(), 1);
+        assert_eq!(CubieCube::from(&vec![R]).order(), 4);
+        assert_eq!(CubieCube::from(&vec![R, U, R3, U3]).order(), 6);
+        assert_eq!(CubieCube::from(&vec![R, U]).order(), 105);
+
+        // T permutation
+        let t_perm = vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3];
+        assert_eq!(CubieCube::from(&t_perm).order(), 2);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let state = CubieCube::from(&vec![R, U, R3, U3]);
+        let corners = state.corner_cycles();
+        let edges = state.edge_cycles();
+
+        assert_eq!(corners.len(), 2);
+        assert!(corners.iter().all(|c| c.positions.len() == 2));
+        assert_eq!(
+            edges,
+            vec![Cycle {
+                positions: vec![FR, UB, UR],
+                orientation: 0
+            }]
+        );
+
+        let superflip = CubieCube {
+            eo: [1; 12],
+            ..Default::default()
+        };
+        let edges = superflip.edge_cycles();
+
+        assert!(superflip.corner_cycles().is_empty());
+        assert_eq!(edges.len(), 12);
+        assert!(edges
+            .iter()
+            .all(|c| c.positions.len() == 1 && c.orientation == 1));
+        assert_eq!(superflip.order(), 2);
+    }
+
+    #[test]
+    fn test_piece_queries() {
+        let state = CubieCube::from(&vec![R]);
+
+        assert_eq!(state.corner_at(UFR), (DFR, 2));
+        assert_eq!(state.locate_corner(DFR), Some((UFR, 2)));
+        assert_eq!(state.edge_at(UR), (FR, 0));
+        assert_eq!(state.locate_edge(FR), Some((UR, 0)));
+        assert!(state.is_corner_solved(UFL));
+        assert!(!state.is_corner_solved(UFR));
+        assert!(state.is_edge_solved(UF));
+        assert!(!state.is_edge_solved(DR));
+        assert_eq!(
+            state.misplaced_corners().collect::<Vec<_>>(),
+            vec![UBR, UFR, DFR, DBR]
+        );
+        assert_eq!(
+            state.misplaced_edges().collect::<Vec<_>>(),
+            vec![BR, FR, UR, DR]
+        );
+        assert_eq!(state.misoriented_corners().count(), 4);
+        assert_eq!(state.misoriented_edges().count(), 0);
+        assert!(!state.is_solved());
+
+        let state = CubieCube::from(&vec![F]);
+        assert_eq!(
+            state.misoriented_edges().collect::<Vec<_>>(),
+            vec![FR, FL, UF, DF]
+        );
+    }
+
     #[test]
     fn test_twist_count() {
         let state = CubieCube::default();
@@ -364,4 +654,22 @@ mod test {
         assert_eq!(state.count_corner_twist(), 3);
         assert_eq!(state.count_edge_twist(), 2);
     }
+
+    #[test]
+    fn test_hash_ord() {
+        use std::collections::HashSet;
+
+        let sexy = CubieCube::from(&vec![R, U, R3, U3]);
+        let states = (0..6)
+            .scan(CubieCube::default(), |state, _| {
+                *state = *state * sexy;
+                Some(*state)
+            })
+            .collect::<HashSet<_>>();
+
+        assert_eq!(states.len(), 6);
+        assert!(states.contains(&CubieCube::default()));
+        assert!(CubieCube::default() < sexy);
+        assert_eq!(states.iter().min(), Some(&CubieCube::default()));
+    }
 }
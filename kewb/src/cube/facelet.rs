@@ -60,9 +60,7 @@ impl Default for FaceCube {
 impl TryFrom<&CubieCube> for FaceCube {
     type Error = Error;
     fn try_from(value: &CubieCube) -> Result<Self, Self::Error> {
-        if !value.is_solvable() {
-            return Err(Error::InvalidCubieValue);
-        }
+        value.validate().into_result()?;
 
         let mut face = FaceCube::default();
 
@@ -139,13 +137,124 @@ impl fmt::Display for FaceCube {
 /// ```
 #[rustfmt::skip]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Facelet {
-    U1, U2, U3, U4, _U5, U6, U7, U8, U9,
-    R1, R2, R3, R4, _R5, R6, R7, R8, R9,
-    F1, F2, F3, F4, _F5, F6, F7, F8, F9,
-    D1, D2, D3, D4, _D5, D6, D7, D8, D9,
-    L1, L2, L3, L4, _L5, L6, L7, L8, L9,
-    B1, B2, B3, B4, _B5, B6, B7, B8, B9,
+    U1, U2, U3, U4, U5, U6, U7, U8, U9,
+    R1, R2, R3, R4, R5, R6, R7, R8, R9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9,
+    D1, D2, D3, D4, D5, D6, D7, D8, D9,
+    L1, L2, L3, L4, L5, L6, L7, L8, L9,
+    B1, B2, B3, B4, B5, B6, B7, B8, B9,
+}
+
+#[rustfmt::skip]
+const FACELETS: [Facelet; 54] = {
+    use Facelet::*;
+    [
+        U1, U2, U3, U4, U5, U6, U7, U8, U9,
+        R1, R2, R3, R4, R5, R6, R7, R8, R9,
+        F1, F2, F3, F4, F5, F6, F7, F8, F9,
+        D1, D2, D3, D4, D5, D6, D7, D8, D9,
+        L1, L2, L3, L4, L5, L6, L7, L8, L9,
+        B1, B2, B3, B4, B5, B6, B7, B8, B9,
+    ]
+};
+
+impl fmt::Display for Facelet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl TryFrom<u8> for Facelet {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        FACELETS
+            .get(value as usize)
+            .copied()
+            .ok_or(Error::InvalidFaceletValue)
+    }
+}
+
+impl Facelet {
+    /// Returns the facelet at the given row and column of a face, both counted from 0.
+    /// Faces are read as laid out in the net, see `Facelet`.
+    pub fn new(face: Color, row: usize, col: usize) -> Result<Self, Error> {
+        if row > 2 || col > 2 {
+            return Err(Error::InvalidFaceletValue);
+        }
+
+        Ok(FACELETS[9 * face as usize + 3 * row + col])
+    }
+
+    /// Returns the face holding the facelet.
+    pub fn get_face(&self) -> Color {
+        SOLVED_FACE_CUBE.f[*self as usize]
+    }
+
+    /// Returns the row of the facelet on its face.
+    pub fn get_row(&self) -> usize {
+        *self as usize % 9 / 3
+    }
+
+    /// Returns the column of the facelet on its face.
+    pub fn get_col(&self) -> usize {
+        *self as usize % 3
+    }
+
+    /// Returns the 9 facelets of a face in reading order.
+    pub fn get_face_facelets(face: Color) -> [Facelet; 9] {
+        let start = 9 * face as usize;
+        std::array::from_fn(|i| FACELETS[start + i])
+    }
+}
+
+impl FaceCube {
+    /// Returns the color of a sticker.
+    pub fn get(&self, facelet: Facelet) -> Color {
+        self.f[facelet as usize]
+    }
+
+    /// Sets the color of a sticker, the state is only checked when converting it.
+    pub fn set(&mut self, facelet: Facelet, color: Color) {
+        self.f[facelet as usize] = color;
+    }
+
+    /// Returns the 3x3 grid of a face, indexed by row then column.
+    pub fn get_face(&self, face: Color) -> [[Color; 3]; 3] {
+        let start = 9 * face as usize;
+        std::array::from_fn(|r| std::array::from_fn(|c| self.f[start + 3 * r + c]))
+    }
+
+    /// Replaces the 3x3 grid of a face, indexed by row then column.
+    pub fn set_face(&mut self, face: Color, grid: [[Color; 3]; 3]) {
+        for (facelet, color) in Facelet::get_face_facelets(face)
+            .iter()
+            .zip(grid.iter().flatten())
+        {
+            self.set(*facelet, *color);
+        }
+    }
+
+    /// Iterates over the stickers of a face in reading order.
+    pub fn iter_face(&self, face: Color) -> impl Iterator<Item = (Facelet, Color)> + '_ {
+        Facelet::get_face_facelets(face)
+            .into_iter()
+            .map(|facelet| (facelet, self.get(facelet)))
+    }
+
+    /// Rotates the stickers of a face clockwise `power` times, leaving the other faces unchanged.
+    /// This is not a move: it fixes a face scanned in the wrong orientation for instance.
+    pub fn rotate_face(&mut self, face: Color, power: u8) {
+        for _ in 0..power % 4 {
+            let grid = self.get_face(face);
+            self.set_face(
+                face,
+                std::array::from_fn(|r| std::array::from_fn(|c| grid[2 - c][r])),
+            );
+        }
+    }
 }
 
 /// Map the corner positions to facelet positions.
@@ -248,4 +357,54 @@ mod test {
 
         assert_eq!(face_cube, expected);
     }
+
+    #[test]
+    fn test_stickers() {
+        let mut face_cube = FaceCube::default();
+
+        assert_eq!(Facelet::new(Color::F, 0, 2).unwrap(), Facelet::F3);
+        assert!(Facelet::new(Color::F, 3, 0).is_err());
+        assert_eq!(Facelet::try_from(49).unwrap(), Facelet::B5);
+        assert_eq!(Facelet::L6.get_face(), Color::L);
+        assert_eq!((Facelet::L6.get_row(), Facelet::L6.get_col()), (1, 2));
+
+        face_cube.set(Facelet::U1, Color::R);
+        face_cube.set(Facelet::U2, Color::F);
+
+        assert_eq!(face_cube.get(Facelet::U1), Color::R);
+        assert_eq!(face_cube.get_face(Color::U)[0][..2], [Color::R, Color::F]);
+        assert_eq!(
+            face_cube.iter_face(Color::U).nth(1),
+            Some((Facelet::U2, Color::F))
+        );
+
+        face_cube.rotate_face(Color::U, 1);
+        assert_eq!(face_cube.get(Facelet::U3), Color::R);
+        assert_eq!(face_cube.get(Facelet::U6), Color::F);
+
+        face_cube.rotate_face(Color::U, 3);
+        assert_eq!(face_cube.get(Facelet::U1), Color::R);
+    }
+
+    #[test]
+    fn test_rotate_face() {
+        // clockwise, the sticker at index `to` comes from index `from` of the U face
+        let clockwise = [6, 3, 0, 7, 4, 1, 8, 5, 2];
+        let counter_clockwise = [2, 5, 8, 1, 4, 7, 0, 3, 6];
+        let cube = CubieCube::from(&vec![crate::Move::R, crate::Move::F]);
+        let state = FaceCube::try_from(&cube).unwrap();
+
+        for (power, sources) in [(1, clockwise), (3, counter_clockwise)] {
+            let mut face_cube = FaceCube::try_from(&cube).unwrap();
+            face_cube.rotate_face(Color::U, power);
+
+            assert_ne!(face_cube, state);
+
+            for (to, from) in sources.into_iter().enumerate() {
+                assert_eq!(face_cube.f[to], state.f[from]);
+            }
+
+            assert_eq!(face_cube.f[9..], state.f[9..]);
+        }
+    }
 }
@@ -0,0 +1,200 @@
+use crate::error::Error;
+
+use super::facelet::{Color, FaceCube, Facelet};
+
+/// Layouts of the string representation of a cube.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FaceletFormat {
+    /// Faces in the U-R-F-D-L-B order, the layout of `FaceCube`.
+    Kociemba,
+    /// Faces in the D-L-B-U-R-F order, used by cstimer.
+    Cstimer,
+    /// Faces in the U-L-F-R-B-D order, reading the net of the WCA scramble images.
+    Wca,
+    /// Mike Reid's piece notation, the pieces at `UF UR UB UL DF DR DB DL FR FL BR BL`
+    /// then `UFR URB UBL ULF DRF DFL DLB DBR`. Solved: `UF UR UB UL ... UFR URB UBL ULF ...`.
+    Reid,
+}
+
+/// Facelets of the edge positions of the Reid notation, in the order of the position name.
+const REID_EDGES: [[Facelet; 2]; 12] = [
+    /*UF=*/ [Facelet::U8, Facelet::F2],
+    /*UR=*/ [Facelet::U6, Facelet::R2],
+    /*UB=*/ [Facelet::U2, Facelet::B2],
+    /*UL=*/ [Facelet::U4, Facelet::L2],
+    /*DF=*/ [Facelet::D2, Facelet::F8],
+    /*DR=*/ [Facelet::D6, Facelet::R8],
+    /*DB=*/ [Facelet::D8, Facelet::B8],
+    /*DL=*/ [Facelet::D4, Facelet::L8],
+    /*FR=*/ [Facelet::F6, Facelet::R4],
+    /*FL=*/ [Facelet::F4, Facelet::L6],
+    /*BR=*/ [Facelet::B4, Facelet::R6],
+    /*BL=*/ [Facelet::B6, Facelet::L4],
+];
+
+/// Facelets of the corner positions of the Reid notation, in the order of the position name.
+const REID_CORNERS: [[Facelet; 3]; 8] = [
+    /*UFR=*/ [Facelet::U9, Facelet::F3, Facelet::R1],
+    /*URB=*/ [Facelet::U3, Facelet::R3, Facelet::B1],
+    /*UBL=*/ [Facelet::U1, Facelet::B3, Facelet::L1],
+    /*ULF=*/ [Facelet::U7, Facelet::L3, Facelet::F1],
+    /*DRF=*/ [Facelet::D3, Facelet::R7, Facelet::F9],
+    /*DFL=*/ [Facelet::D1, Facelet::F7, Facelet::L9],
+    /*DLB=*/ [Facelet::D7, Facelet::L7, Facelet::B9],
+    /*DBR=*/ [Facelet::D9, Facelet::B7, Facelet::R9],
+];
+
+impl FaceletFormat {
+    /// Returns the order of the faces in the string, `None` for the piece notation.
+    fn face_order(&self) -> Option<[Color; 6]> {
+        use Color::*;
+
+        match self {
+            FaceletFormat::Kociemba => Some([U, R, F, D, L, B]),
+            FaceletFormat::Cstimer => Some([D, L, B, U, R, F]),
+            FaceletFormat::Wca => Some([U, L, F, R, B, D]),
+            FaceletFormat::Reid => None,
+        }
+    }
+}
+
+fn reid_pieces() -> impl Iterator<Item = &'static [Facelet]> {
+    REID_EDGES
+        .iter()
+        .map(|e| e.as_slice())
+        .chain(REID_CORNERS.iter().map(|c| c.as_slice()))
+}
+
+fn parse_reid(s: &str) -> Result<FaceCube, Error> {
+    let tokens = s.split_whitespace().collect::<Vec<_>>();
+
+    if tokens.len() != 20 {
+        return Err(Error::InvalidFaceletString);
+    }
+
+    let mut face_cube = FaceCube::default();
+
+    for (token, facelets) in tokens.iter().zip(reid_pieces()) {
+        if token.chars().count() != facelets.len() {
+            return Err(Error::InvalidFaceletString);
+        }
+
+        for (c, f) in token.chars().zip(facelets) {
+            face_cube.f[*f as usize] = Color::try_from(c)?;
+        }
+    }
+
+    Ok(face_cube)
+}
+
+impl FaceCube {
+    /// Parses the string representation of a cube in the given format.
+    pub fn from_format(s: &str, format: FaceletFormat) -> Result<Self, Error> {
+        let Some(order) = format.face_order() else {
+            return parse_reid(s);
+        };
+
+        let colors = s
+            .chars()
+            .map(Color::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if colors.len() != 54 {
+            return Err(Error::InvalidFaceletString);
+        }
+
+        let mut face_cube = FaceCube::default();
+
+        for (face, stickers) in order.iter().zip(colors.chunks(9)) {
+            let start = 9 * *face as usize;
+            face_cube.f[start..start + 9].copy_from_slice(stickers);
+        }
+
+        Ok(face_cube)
+    }
+
+    /// Returns the string representation of the cube in the given format.
+    pub fn to_format(&self, format: FaceletFormat) -> String {
+        let Some(order) = format.face_order() else {
+            return reid_pieces()
+                .map(|facelets| {
+                    facelets
+                        .iter()
+                        .map(|f| self.f[*f as usize].to_string())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+        };
+
+        order
+            .iter()
+            .flat_map(|face| {
+                let start = 9 * *face as usize;
+                &self.f[start..start + 9]
+            })
+            .map(|c| c.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CubieCube, Move::*};
+
+    const FORMATS: [FaceletFormat; 4] = [
+        FaceletFormat::Kociemba,
+        FaceletFormat::Cstimer,
+        FaceletFormat::Wca,
+        FaceletFormat::Reid,
+    ];
+
+    #[test]
+    fn test_face_orders() {
+        let solved = FaceCube::default();
+
+        assert_eq!(
+            solved.to_format(FaceletFormat::Cstimer),
+            "DDDDDDDDDLLLLLLLLLBBBBBBBBBUUUUUUUUURRRRRRRRRFFFFFFFFF"
+        );
+        assert_eq!(
+            solved.to_format(FaceletFormat::Wca),
+            "UUUUUUUUULLLLLLLLLFFFFFFFFFRRRRRRRRRBBBBBBBBBDDDDDDDDD"
+        );
+        assert_eq!(
+            solved.to_format(FaceletFormat::Kociemba),
+            solved.to_string()
+        );
+    }
+
+    #[test]
+    fn test_reid() {
+        let solved = "UF UR UB UL DF DR DB DL FR FL BR BL UFR URB UBL ULF DRF DFL DLB DBR";
+        let u = "UR UB UL UF DF DR DB DL FR FL BR BL URB UBL ULF UFR DRF DFL DLB DBR";
+        let state = FaceCube::try_from(&CubieCube::from(&vec![U])).unwrap();
+
+        assert_eq!(FaceCube::default().to_format(FaceletFormat::Reid), solved);
+        assert_eq!(state.to_format(FaceletFormat::Reid), u);
+        assert_eq!(
+            FaceCube::from_format(u, FaceletFormat::Reid).unwrap(),
+            state
+        );
+        assert!(FaceCube::from_format("UF UR", FaceletFormat::Reid).is_err());
+        assert!(FaceCube::from_format(&u.replace("UR", "U"), FaceletFormat::Reid).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let state = CubieCube::from(&vec![R, U2, F3, D, L2, B, U3]);
+        let face_cube = FaceCube::try_from(&state).unwrap();
+
+        for format in FORMATS {
+            let s = face_cube.to_format(format);
+            let parsed = FaceCube::from_format(&s, format).unwrap();
+
+            assert_eq!(parsed, face_cube);
+            assert_eq!(CubieCube::try_from(&parsed).unwrap(), state);
+        }
+    }
+}
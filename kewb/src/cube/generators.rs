@@ -1,4 +1,4 @@
-use crate::constants::{CO_COUNT, CP_COUNT, EO_COUNT, EP_COUNT};
+use crate::constants::{CO_COUNT, CP_COUNT, EO_COUNT, EP_COUNT, STATE_COUNT};
 
 use super::{cubie::CubieCube, index::*};
 use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng, Rng};
@@ -125,19 +125,7 @@ pub fn generate_state_cross_solved() -> CubieCube {
     state
 }
 
-/// Generates a random state on the cubie level.
+/// Generates a random state on the cubie level, uniformly drawn from the state ranks.
 pub fn generate_random_state() -> CubieCube {
-    let mut rng = thread_rng();
-    let mut state = CubieCube {
-        cp: index_to_cp(rng.gen_range(0..CP_COUNT)),
-        co: index_to_co(rng.gen_range(0..CO_COUNT)),
-        ep: index_to_ep(rng.gen_range(0..EP_COUNT)),
-        eo: index_to_eo(rng.gen_range(0..EO_COUNT)),
-    };
-
-    if !state.is_solvable() {
-        fix_parity(&mut state, &mut rng, (0..8).collect(), (0..12).collect())
-    }
-
-    state
+    index_to_state(thread_rng().gen_range(0..STATE_COUNT))
 }
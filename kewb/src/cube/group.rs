@@ -0,0 +1,218 @@
+use super::{
+    cubie::CubieCube,
+    moves::{Move, MOVE_CUBES},
+};
+
+/// Number of stickers moved by the pieces: 3 per corner and 2 per edge.
+const POINTS: usize = 48;
+
+/// Permutation of the stickers, mapping each point to its image.
+type Permutation = [u8; POINTS];
+
+const IDENTITY: Permutation = {
+    let mut p = [0; POINTS];
+    let mut i = 0;
+
+    while i < POINTS {
+        p[i] = i as u8;
+        i += 1;
+    }
+
+    p
+};
+
+/// Returns the permutation applying `b` then `a`.
+fn compose(a: &Permutation, b: &Permutation) -> Permutation {
+    let mut p = IDENTITY;
+
+    for (i, x) in b.iter().enumerate() {
+        p[i] = a[*x as usize];
+    }
+
+    p
+}
+
+fn inverse(a: &Permutation) -> Permutation {
+    let mut p = IDENTITY;
+
+    for (i, x) in a.iter().enumerate() {
+        p[*x as usize] = i as u8;
+    }
+
+    p
+}
+
+/// Returns the permutation of the stickers of the state, multiplying states composes their permutations.
+fn to_permutation(state: &CubieCube) -> Permutation {
+    let mut p = IDENTITY;
+
+    for i in 0..8 {
+        for t in 0..3 {
+            p[3 * i + t] = (3 * state.cp[i] as usize + (t + state.co[i] as usize) % 3) as u8;
+        }
+    }
+
+    for i in 0..12 {
+        for t in 0..2 {
+            p[24 + 2 * i + t] =
+                (24 + 2 * state.ep[i] as usize + (t + state.eo[i] as usize) % 2) as u8;
+        }
+    }
+
+    p
+}
+
+/// Level of a stabilizer chain: the generators fixing the previous base points
+/// and a transversal mapping the base point to every point of its orbit.
+struct Level {
+    base: usize,
+    generators: Vec<Permutation>,
+    transversal: Vec<Option<Permutation>>,
+}
+
+impl Level {
+    fn new(base: usize) -> Self {
+        let mut transversal = vec![None; POINTS];
+        transversal[base] = Some(IDENTITY);
+
+        Self {
+            base,
+            generators: vec![],
+            transversal,
+        }
+    }
+
+    fn orbit(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..POINTS).filter(|p| self.transversal[*p].is_some())
+    }
+
+    /// Extends the orbit with the generators.
+    fn update_orbit(&mut self) {
+        let mut queue: Vec<usize> = self.orbit().collect();
+
+        while let Some(point) = queue.pop() {
+            let u = self.transversal[point].unwrap();
+
+            for g in &self.generators {
+                let image = g[point] as usize;
+
+                if self.transversal[image].is_none() {
+                    self.transversal[image] = Some(compose(g, &u));
+                    queue.push(image);
+                }
+            }
+        }
+    }
+}
+
+/// Group generated by a set of moves, stored as a stabilizer chain built with the Schreier-Sims algorithm.
+pub struct MoveGroup {
+    levels: Vec<Level>,
+}
+
+impl MoveGroup {
+    pub fn new(moves: &[Move]) -> Self {
+        let mut group = Self { levels: vec![] };
+
+        for m in moves {
+            group.extend(0, to_permutation(&MOVE_CUBES[*m as usize]));
+        }
+
+        group
+    }
+
+    /// Divides the permutation by the transversals from the given level,
+    /// returns the remainder and the level where it stopped.
+    fn sift(&self, mut p: Permutation, from: usize) -> (Permutation, usize) {
+        for (i, level) in self.levels.iter().enumerate().skip(from) {
+            match &level.transversal[p[level.base] as usize] {
+                Some(u) => p = compose(&inverse(u), &p),
+                None => return (p, i),
+            }
+        }
+
+        (p, self.levels.len())
+    }
+
+    /// Adds the permutation to the generators of the level, then the Schreier generators to the next levels.
+    fn extend(&mut self, level: usize, p: Permutation) {
+        let (p, _) = self.sift(p, level);
+
+        if p == IDENTITY {
+            return;
+        }
+
+        if level == self.levels.len() {
+            let base = (0..POINTS).find(|i| p[*i] as usize != *i).unwrap();
+            self.levels.push(Level::new(base));
+        }
+
+        self.levels[level].generators.push(p);
+        self.levels[level].update_orbit();
+
+        let current = &self.levels[level];
+        let schreier_generators: Vec<Permutation> = current
+            .orbit()
+            .flat_map(|point| {
+                let u = current.transversal[point].unwrap();
+
+                current.generators.iter().map(move |g| {
+                    let image = g[point] as usize;
+                    let v = current.transversal[image].unwrap();
+
+                    compose(&inverse(&v), &compose(g, &u))
+                })
+            })
+            .collect();
+
+        for s in schreier_generators {
+            self.extend(level + 1, s);
+        }
+    }
+
+    /// Returns the number of states of the group.
+    pub fn order(&self) -> u128 {
+        self.levels
+            .iter()
+            .map(|level| level.orbit().count() as u128)
+            .product()
+    }
+
+    /// Checks if the state can be reached with the moves of the group.
+    pub fn contains(&self, state: &CubieCube) -> bool {
+        let (p, _) = self.sift(to_permutation(state), 0);
+
+        p == IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{generators::generate_random_state, Move::*};
+
+    #[test]
+    fn test_permutation() {
+        let a = generate_random_state();
+        let b = generate_random_state();
+
+        assert_eq!(
+            to_permutation(&(a * b)),
+            compose(&to_permutation(&a), &to_permutation(&b))
+        );
+    }
+
+    #[test]
+    fn test_move_group() {
+        let two_gen = MoveGroup::new(&[R, U]);
+
+        assert_eq!(two_gen.order(), 73_483_200);
+        assert!(two_gen.contains(&CubieCube::from(&vec![R, U2, R3, U3, R2])));
+        assert!(!two_gen.contains(&CubieCube::from(&vec![R, F])));
+        assert_eq!(MoveGroup::new(&[U2]).order(), 2);
+        assert_eq!(
+            MoveGroup::new(&[U, D, R, L, F, B]).order(),
+            43_252_003_274_489_856_000
+        );
+    }
+}
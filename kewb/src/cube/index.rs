@@ -1,4 +1,7 @@
-use super::cubie::{Corner, Edge};
+use super::{
+    constants::{CO_COUNT, EO_COUNT},
+    cubie::{Corner, CubieCube, Edge},
+};
 
 fn fill_orientation_slice(slice: &mut [u8], cases: u8, index: u16) {
     let len = slice.len();
@@ -218,6 +221,44 @@ pub fn index_to_eo_f2l(index: u16) -> [u8; 12] {
     eo
 }
 
+/// The count of edge permutations of a given parity.
+const EP_HALF_COUNT: u128 = 239_500_800;
+
+/// Ranks a solvable state among the `STATE_COUNT` states.
+/// The edge permutation parity follows the corner one, so only half of the edge permutations are counted:
+/// the permutations of rank 2k and 2k + 1 only differ by a swap of the last two edges.
+pub fn state_to_index(state: &CubieCube) -> u128 {
+    let cp = cp_to_index(&state.cp) as u128;
+    let co = co_to_index(&state.co) as u128;
+    let ep = ep_to_index(&state.ep) as u128 / 2;
+    let eo = eo_to_index(&state.eo) as u128;
+
+    ((cp * CO_COUNT as u128 + co) * EP_HALF_COUNT + ep) * EO_COUNT as u128 + eo
+}
+
+/// Returns the state with the given rank, the index must be lower than `STATE_COUNT`.
+pub fn index_to_state(index: u128) -> CubieCube {
+    let eo = (index % EO_COUNT as u128) as u16;
+    let index = index / EO_COUNT as u128;
+    let ep = (index % EP_HALF_COUNT) as u32 * 2;
+    let index = index / EP_HALF_COUNT;
+    let co = (index % CO_COUNT as u128) as u16;
+    let cp = (index / CO_COUNT as u128) as u16;
+
+    let mut state = CubieCube {
+        cp: index_to_cp(cp),
+        co: index_to_co(co),
+        ep: index_to_ep(ep),
+        eo: index_to_eo(eo),
+    };
+
+    if state.count_edge_perm() % 2 != state.count_corner_perm() % 2 {
+        state.ep = index_to_ep(ep + 1);
+    }
+
+    state
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -299,4 +340,30 @@ mod test {
         assert_eq!(ep_to_index(&edges), 479001599);
         assert_eq!(index_to_ep(479001599), edges);
     }
+
+    #[test]
+    fn test_state() {
+        use crate::cube::{constants::STATE_COUNT, moves::Move::*};
+
+        assert_eq!(state_to_index(&SOLVED_CUBIE_CUBE), 0);
+        assert_eq!(index_to_state(0), SOLVED_CUBIE_CUBE);
+        assert!(index_to_state(STATE_COUNT - 1).is_solvable());
+        assert_eq!(
+            state_to_index(&index_to_state(STATE_COUNT - 1)),
+            STATE_COUNT - 1
+        );
+
+        let state = CubieCube::from(&vec![R, U, F3, D2, L, B3]);
+        let index = state_to_index(&state);
+
+        assert!(index < STATE_COUNT);
+        assert_eq!(index_to_state(index), state);
+
+        for index in [1, 2, 12345678901234567890, STATE_COUNT / 3] {
+            let state = index_to_state(index);
+
+            assert!(state.is_solvable());
+            assert_eq!(state_to_index(&state), index);
+        }
+    }
 }
@@ -0,0 +1,462 @@
+use std::fmt;
+
+use crate::error::Error;
+
+use super::{
+    cubie::{Corner, CubieCube, Edge, SOLVED_CUBIE_CUBE},
+    facelet::{Color, FaceCube, CORNER_COLOR, CORNER_FACELET, EDGE_COLOR, EDGE_FACELET},
+    moves::Move,
+};
+
+/// How much of a piece is known.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieceMask {
+    /// Position and orientation matter.
+    Full,
+    /// Only the orientation matters, the piece is indistinguishable from other such pieces.
+    OrientationOnly,
+    /// The piece does not matter.
+    Ignore,
+}
+
+/// Masks of the pieces of the cube, indexed by piece using the order of `Corner` and `Edge`.
+/// The mask follows the pieces when they move.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mask {
+    pub corners: [PieceMask; 8],
+    pub edges: [PieceMask; 12],
+}
+
+impl Default for Mask {
+    fn default() -> Self {
+        Self {
+            corners: [PieceMask::Full; 8],
+            edges: [PieceMask::Full; 12],
+        }
+    }
+}
+
+const U_CORNERS: [Corner; 4] = [Corner::UBL, Corner::UBR, Corner::UFR, Corner::UFL];
+const U_EDGES: [Edge; 4] = [Edge::UB, Edge::UR, Edge::UF, Edge::UL];
+const D_EDGES: [Edge; 4] = [Edge::DF, Edge::DR, Edge::DB, Edge::DL];
+
+impl Mask {
+    pub fn with_corner(mut self, corner: Corner, mask: PieceMask) -> Self {
+        self.corners[corner as usize] = mask;
+        self
+    }
+
+    pub fn with_edge(mut self, edge: Edge, mask: PieceMask) -> Self {
+        self.edges[edge as usize] = mask;
+        self
+    }
+
+    /// Only the D layer cross edges matter.
+    pub fn cross() -> Self {
+        let mask = Self {
+            corners: [PieceMask::Ignore; 8],
+            edges: [PieceMask::Ignore; 12],
+        };
+
+        D_EDGES
+            .iter()
+            .fold(mask, |mask, e| mask.with_edge(*e, PieceMask::Full))
+    }
+
+    /// The first two layers matter, the U layer pieces are ignored.
+    pub fn f2l() -> Self {
+        Self::last_layer(PieceMask::Ignore)
+    }
+
+    /// The first two layers matter and the U layer pieces must be oriented.
+    pub fn oll() -> Self {
+        Self::last_layer(PieceMask::OrientationOnly)
+    }
+
+    fn last_layer(mask: PieceMask) -> Self {
+        let corners = U_CORNERS
+            .iter()
+            .fold(Self::default(), |m, c| m.with_corner(*c, mask));
+
+        U_EDGES.iter().fold(corners, |m, e| m.with_edge(*e, mask))
+    }
+}
+
+/// What is known about the piece at a position.
+#[derive(Debug, PartialEq)]
+enum Slot<T> {
+    Known(T, u8),
+    Oriented(u8),
+    Unknown,
+}
+
+fn slot<T>(piece: T, ori: u8, mask: PieceMask) -> Slot<T> {
+    match mask {
+        PieceMask::Full => Slot::Known(piece, ori),
+        PieceMask::OrientationOnly => Slot::Oriented(ori),
+        PieceMask::Ignore => Slot::Unknown,
+    }
+}
+
+/// Cube state where some pieces are unknown or only their orientation is known.
+/// Two masked cubes are equal when every position shows the same information.
+#[derive(Debug, Clone, Copy)]
+pub struct MaskedCube {
+    pub state: CubieCube,
+    pub mask: Mask,
+}
+
+impl MaskedCube {
+    pub fn new(state: CubieCube, mask: Mask) -> Self {
+        Self { state, mask }
+    }
+
+    pub fn apply_move(self, m: Move) -> Self {
+        Self::new(self.state.apply_move(m), self.mask)
+    }
+
+    pub fn apply_moves(&self, moves: &[Move]) -> Self {
+        Self::new(self.state.apply_moves(moves), self.mask)
+    }
+
+    fn corner_slot(&self, position: usize) -> Slot<Corner> {
+        let corner = self.state.cp[position];
+        let mask = self.mask.corners[corner as usize];
+        slot(corner, self.state.co[position], mask)
+    }
+
+    fn edge_slot(&self, position: usize) -> Slot<Edge> {
+        let edge = self.state.ep[position];
+        let mask = self.mask.edges[edge as usize];
+        slot(edge, self.state.eo[position], mask)
+    }
+
+    /// Checks that every known piece is solved and every oriented piece has a correct orientation.
+    pub fn is_solved(&self) -> bool {
+        *self == Self::new(SOLVED_CUBIE_CUBE, self.mask)
+    }
+}
+
+impl PartialEq for MaskedCube {
+    fn eq(&self, other: &Self) -> bool {
+        (0..8).all(|i| self.corner_slot(i) == other.corner_slot(i))
+            && (0..12).all(|i| self.edge_slot(i) == other.edge_slot(i))
+    }
+}
+
+impl From<CubieCube> for MaskedCube {
+    fn from(state: CubieCube) -> Self {
+        Self::new(state, Mask::default())
+    }
+}
+
+impl CubieCube {
+    /// Checks if the pieces selected by the mask are solved.
+    /// Example: `state.is_solved_with(&Mask::f2l())` checks that the first two layers are solved.
+    pub fn is_solved_with(&self, mask: &Mask) -> bool {
+        MaskedCube::new(*self, *mask).is_solved()
+    }
+}
+
+/// Cube on the facelet level where some stickers are unknown, shown as `X`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MaskedFaceCube {
+    pub f: [Option<Color>; 54],
+}
+
+impl From<&FaceCube> for MaskedFaceCube {
+    fn from(face_cube: &FaceCube) -> Self {
+        Self {
+            f: face_cube.f.map(Some),
+        }
+    }
+}
+
+impl From<&MaskedCube> for MaskedFaceCube {
+    /// Only the sticker giving the orientation is shown for oriented pieces:
+    /// the U or D sticker, or the F or B sticker for the middle layer edges.
+    fn from(value: &MaskedCube) -> Self {
+        let mut face_cube = FaceCube::default().f.map(Some);
+
+        for (i, facelets) in CORNER_FACELET.iter().enumerate() {
+            let corner = value.state.cp[i] as usize;
+            let co = value.state.co[i] as usize;
+
+            for (j, f) in facelets.iter().enumerate() {
+                let k = (j + 3 - co) % 3;
+                face_cube[*f as usize] = match value.mask.corners[corner] {
+                    PieceMask::Full => Some(CORNER_COLOR[corner][k]),
+                    PieceMask::OrientationOnly if k == 0 => Some(CORNER_COLOR[corner][k]),
+                    _ => None,
+                };
+            }
+        }
+
+        for (i, facelets) in EDGE_FACELET.iter().enumerate() {
+            let edge = value.state.ep[i] as usize;
+            let eo = value.state.eo[i] as usize;
+
+            for (j, f) in facelets.iter().enumerate() {
+                let k = (j + eo) % 2;
+                face_cube[*f as usize] = match value.mask.edges[edge] {
+                    PieceMask::Full => Some(EDGE_COLOR[edge][k]),
+                    PieceMask::OrientationOnly if k == 0 => Some(EDGE_COLOR[edge][k]),
+                    _ => None,
+                };
+            }
+        }
+
+        Self { f: face_cube }
+    }
+}
+
+/// Pieces and orientations matching the known stickers at a position.
+fn candidates<const S: usize>(
+    stickers: [Option<Color>; S],
+    colors: &[[Color; S]],
+    index: impl Fn(usize, usize) -> usize,
+) -> Vec<(usize, u8)> {
+    let mut found = Vec::new();
+
+    for (piece, piece_colors) in colors.iter().enumerate() {
+        for ori in 0..S {
+            let matches = stickers
+                .iter()
+                .enumerate()
+                .all(|(k, s)| s.is_none_or(|c| c == piece_colors[index(k, ori)]));
+
+            if matches {
+                found.push((piece, ori as u8));
+            }
+        }
+    }
+
+    found
+}
+
+/// Pieces, orientations and masks of the positions.
+type Decoded<const N: usize> = ([usize; N], [u8; N], [PieceMask; N]);
+
+/// Decodes the positions from their candidates, unknown pieces get the pieces left over.
+/// Only identified pieces are fully masked, an oriented position takes one of its candidates
+/// when one is left and the other leftover pieces are ignored.
+/// Fails with the index of a piece found twice.
+fn decode<const N: usize>(found: [Vec<(usize, u8)>; N]) -> Result<Decoded<N>, usize> {
+    let mut pieces = [None; N];
+    let mut oris = [0; N];
+    let mut masks = [PieceMask::Ignore; N];
+    let mut used = [false; N];
+
+    for (i, candidates) in found.iter().enumerate() {
+        if let [(piece, ori)] = candidates[..] {
+            if used[piece] {
+                return Err(piece);
+            }
+
+            used[piece] = true;
+            pieces[i] = Some(piece);
+            oris[i] = ori;
+            masks[piece] = PieceMask::Full;
+        }
+    }
+
+    for (i, candidates) in found.iter().enumerate() {
+        let ori = candidates[0].1;
+
+        if pieces[i].is_some() || candidates.iter().any(|(_, o)| *o != ori) {
+            continue;
+        }
+
+        if let Some((piece, _)) = candidates.iter().find(|(p, _)| !used[*p]) {
+            used[*piece] = true;
+            pieces[i] = Some(*piece);
+            oris[i] = ori;
+            masks[*piece] = PieceMask::OrientationOnly;
+        }
+    }
+
+    let mut left = (0..N).filter(|p| !used[*p]);
+
+    for piece in pieces.iter_mut().filter(|p| p.is_none()) {
+        *piece = left.next();
+    }
+
+    Ok((pieces.map(|p| p.unwrap()), oris, masks))
+}
+
+impl TryFrom<&MaskedFaceCube> for MaskedCube {
+    type Error = Error;
+
+    /// A piece is known when its stickers match a single piece, and oriented when
+    /// every matching piece has the same orientation, a lone U sticker on a corner for instance.
+    fn try_from(value: &MaskedFaceCube) -> Result<Self, Self::Error> {
+        let mut corners = Vec::new();
+        let mut edges = Vec::new();
+
+        for (i, facelets) in CORNER_FACELET.iter().enumerate() {
+            let stickers = facelets.map(|f| value.f[f as usize]);
+            let found = candidates(stickers, &CORNER_COLOR, |k, ori| (k + 3 - ori) % 3);
+
+            if found.is_empty() {
+                return Err(Error::InvalidCornerColors(SOLVED_CUBIE_CUBE.cp[i]));
+            }
+
+            corners.push(found);
+        }
+
+        for (i, facelets) in EDGE_FACELET.iter().enumerate() {
+            let stickers = facelets.map(|f| value.f[f as usize]);
+            let found = candidates(stickers, &EDGE_COLOR, |k, ori| (k + ori) % 2);
+
+            if found.is_empty() {
+                return Err(Error::InvalidEdgeColors(SOLVED_CUBIE_CUBE.ep[i]));
+            }
+
+            edges.push(found);
+        }
+
+        let (cp, co, corner_masks) = decode::<8>(corners.try_into().unwrap())
+            .map_err(|c| Error::DuplicateCorner(SOLVED_CUBIE_CUBE.cp[c]))?;
+        let (ep, eo, edge_masks) = decode::<12>(edges.try_into().unwrap())
+            .map_err(|e| Error::DuplicateEdge(SOLVED_CUBIE_CUBE.ep[e]))?;
+
+        let state = CubieCube {
+            cp: cp.map(|c| SOLVED_CUBIE_CUBE.cp[c]),
+            co,
+            ep: ep.map(|e| SOLVED_CUBIE_CUBE.ep[e]),
+            eo,
+        };
+        let mask = Mask {
+            corners: corner_masks,
+            edges: edge_masks,
+        };
+
+        Ok(MaskedCube::new(state, mask))
+    }
+}
+
+impl TryFrom<&str> for MaskedFaceCube {
+    type Error = Error;
+
+    fn try_from(cube_string: &str) -> Result<Self, Self::Error> {
+        if cube_string.chars().count() != 54 {
+            return Err(Error::InvalidFaceletString);
+        }
+
+        let mut face_cube = Self { f: [None; 54] };
+
+        for (i, c) in cube_string.chars().enumerate() {
+            face_cube.f[i] = match c {
+                'X' => None,
+                c => Some(Color::try_from(c)?),
+            };
+        }
+
+        Ok(face_cube)
+    }
+}
+
+impl fmt::Display for MaskedFaceCube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self
+            .f
+            .iter()
+            .map(|c| c.map_or("X".to_owned(), |c| c.to_string()))
+            .collect::<String>();
+
+        write!(f, "{s}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{transform::invert, Move::*};
+
+    #[test]
+    fn test_solved_with_mask() {
+        let sune = vec![R, U, R3, U, R, U2, R3];
+        let t_perm = vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3];
+        let sexy = vec![R, U, R3, U3];
+
+        assert!(CubieCube::from(&sune).is_solved_with(&Mask::f2l()));
+        assert!(!CubieCube::from(&sune).is_solved_with(&Mask::oll()));
+        assert!(CubieCube::from(&t_perm).is_solved_with(&Mask::oll()));
+        assert!(!CubieCube::from(&t_perm).is_solved_with(&Mask::default()));
+        assert!(!CubieCube::from(&sexy).is_solved_with(&Mask::f2l()));
+        assert!(!CubieCube::from(&vec![U, R2, D]).is_solved_with(&Mask::cross()));
+        assert!(CubieCube::from(&vec![U, R, U3, R3]).is_solved_with(&Mask::cross()));
+    }
+
+    #[test]
+    fn test_masked_equality() {
+        // any last layer state is solved by an alg when only the F2L matters
+        let case = MaskedCube::new(CubieCube::from(&invert(&[R, U, R3])), Mask::f2l());
+
+        assert!(case.apply_moves(&[R, U, R3]).is_solved());
+        assert!(case.apply_moves(&[R, U, R3, U2]).is_solved());
+        assert!(!case.apply_moves(&[U, R, U, R3]).is_solved());
+        assert_eq!(case.apply_moves(&[U2, U2]), case);
+        assert_eq!(
+            case.apply_moves(&[R, U, R3, U]),
+            case.apply_moves(&[R, U, R3])
+        );
+    }
+
+    #[test]
+    fn test_masked_facelets() {
+        let state = CubieCube::from(&vec![R, U, R3, U, R, U2, R3]);
+        let masked = MaskedCube::new(state, Mask::oll());
+        let face_cube = MaskedFaceCube::from(&masked);
+        let s = face_cube.to_string();
+
+        assert_eq!(s.matches('X').count(), 12);
+        assert_eq!(MaskedFaceCube::try_from(s.as_str()).unwrap(), face_cube);
+
+        let decoded = MaskedCube::try_from(&face_cube).unwrap();
+
+        assert_eq!(decoded, masked);
+        assert_eq!(MaskedFaceCube::from(&decoded), face_cube);
+        assert!(decoded.apply_moves(&[R, U2, R3, U3, R, U3, R3]).is_solved());
+    }
+
+    #[test]
+    fn test_unknown_stickers() {
+        // F2L solved and the last layer completely unknown
+        let s = "XXXXUXXXXXXXRRRRRRXXXFFFFFFDDDDDDDDDXXXLLLLLLXXXBBBBBB";
+        let masked = MaskedCube::try_from(&MaskedFaceCube::try_from(s).unwrap()).unwrap();
+
+        assert_eq!(masked.mask, Mask::f2l());
+        assert!(masked.is_solved());
+        assert!(!masked.apply_move(R).is_solved());
+
+        let mut face_cube = MaskedFaceCube::try_from(s).unwrap();
+        face_cube.f[27] = Some(Color::R);
+        assert!(MaskedCube::try_from(&face_cube).is_err());
+
+        // the oriented corner takes a U corner, the pieces left over stay unknown
+        let mut face_cube = MaskedFaceCube::try_from(s).unwrap();
+        face_cube.f[0] = Some(Color::U);
+        let masked = MaskedCube::try_from(&face_cube).unwrap();
+        let oriented = masked.state.cp[0] as usize;
+
+        assert!(oriented < 4);
+        assert_eq!(masked.mask.corners[oriented], PieceMask::OrientationOnly);
+        assert_eq!(
+            masked.mask.corners[..4]
+                .iter()
+                .filter(|m| **m == PieceMask::Ignore)
+                .count(),
+            3
+        );
+
+        // a lone L sticker does not give the orientation of the corner
+        let mut face_cube = MaskedFaceCube::try_from(s).unwrap();
+        face_cube.f[36] = Some(Color::L);
+        let masked = MaskedCube::try_from(&face_cube).unwrap();
+
+        assert_eq!(masked.mask, Mask::f2l());
+    }
+}
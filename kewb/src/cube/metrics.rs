@@ -0,0 +1,93 @@
+use std::fmt;
+
+use super::{moves::Move, notation::ExtMove};
+
+/// Move count metrics.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Metric {
+    /// Half turn metric: any face turn counts as 1, slices as 2, rotations are free.
+    Htm,
+    /// Quarter turn metric: quarter turns count as 1, half turns as 2, rotations are free.
+    Qtm,
+    /// Slice turn metric: any face or slice turn counts as 1, rotations are free.
+    Stm,
+    /// Execution turn metric: any move, including rotations, counts as 1.
+    Etm,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!("{:?}", self).to_uppercase();
+        write!(f, "{name}")
+    }
+}
+
+/// Moves which can be counted in the different metrics.
+pub trait MoveCount {
+    /// Returns the cost of the move in the given metric.
+    fn count(&self, metric: Metric) -> usize;
+}
+
+fn quarter_turns(power: u8) -> usize {
+    match power {
+        2 => 2,
+        _ => 1,
+    }
+}
+
+impl MoveCount for Move {
+    fn count(&self, metric: Metric) -> usize {
+        match metric {
+            Metric::Qtm => quarter_turns(self.get_power()),
+            _ => 1,
+        }
+    }
+}
+
+impl MoveCount for ExtMove {
+    fn count(&self, metric: Metric) -> usize {
+        match (self, metric) {
+            (_, Metric::Etm) => 1,
+            (ExtMove::Rotation(_), _) => 0,
+            (ExtMove::Face(m) | ExtMove::Wide(m), metric) => m.count(metric),
+            (ExtMove::Slice(_), Metric::Htm) => 2,
+            (ExtMove::Slice(m), Metric::Qtm) => 2 * quarter_turns(m.get_power()),
+            (ExtMove::Slice(_), Metric::Stm) => 1,
+        }
+    }
+}
+
+/// Counts the moves of a sequence in the given metric.
+pub fn move_count<M: MoveCount>(moves: &[M], metric: Metric) -> usize {
+    moves.iter().map(|m| m.count(metric)).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{algorithm::Algorithm, Move::*};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_face_moves() {
+        let moves = [R, U2, R3, U3, F2];
+
+        assert_eq!(move_count(&moves, Metric::Htm), 5);
+        assert_eq!(move_count(&moves, Metric::Qtm), 7);
+        assert_eq!(move_count(&moves, Metric::Stm), 5);
+        assert_eq!(move_count(&moves, Metric::Etm), 5);
+    }
+
+    #[test]
+    fn test_extended_moves() {
+        let moves = Algorithm::from_str("x M2 U M' U2 M U Rw y'")
+            .unwrap()
+            .expand();
+
+        assert_eq!(move_count(&moves, Metric::Htm), 10);
+        assert_eq!(move_count(&moves, Metric::Qtm), 13);
+        assert_eq!(move_count(&moves, Metric::Stm), 7);
+        assert_eq!(move_count(&moves, Metric::Etm), 9);
+    }
+}
@@ -1,8 +1,24 @@
+pub mod algorithm;
 pub mod constants;
 pub mod cubie;
 pub mod facelet;
+pub mod format;
 pub mod generators;
+pub mod group;
 pub mod index;
+pub mod mask;
+pub mod metrics;
 pub mod moves;
+pub mod notation;
+pub mod packed;
+pub mod scheme;
 pub mod scramble;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod simplify;
+pub mod supercube;
+pub mod svg;
+pub mod symmetry;
+pub mod transform;
 mod utils;
+pub mod validation;
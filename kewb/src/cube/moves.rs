@@ -3,7 +3,10 @@ use std::{fmt, str::FromStr};
 use crate::error::Error;
 
 use self::Move::*;
-use super::cubie::{Corner::*, CubieCube, Edge::*};
+use super::{
+    cubie::{multiply, Corner::*, CubieCube, Edge::*},
+    facelet::Color,
+};
 
 /// Layer moves, Up, Down, Right, Left, Face, Back.
 /// $ clockwise, $2 double, $3 counter-clockwise.
@@ -101,6 +104,41 @@ impl Move {
             _ => self,
         }
     }
+
+    /// Returns the face turned by the move.
+    pub fn get_face(&self) -> Color {
+        match self {
+            U | U2 | U3 => Color::U,
+            D | D2 | D3 => Color::D,
+            R | R2 | R3 => Color::R,
+            L | L2 | L3 => Color::L,
+            F | F2 | F3 => Color::F,
+            B | B2 | B3 => Color::B,
+        }
+    }
+
+    /// Returns the number of clockwise quarter turns: 1, 2 or 3.
+    pub fn get_power(&self) -> u8 {
+        *self as u8 % 3 + 1
+    }
+
+    /// Builds the move turning `face` by `power` clockwise quarter turns.
+    /// Returns `None` when the power is a multiple of 4.
+    pub fn from_face(face: Color, power: u8) -> Option<Self> {
+        let moves = match face {
+            Color::U => [U, U2, U3],
+            Color::D => [D, D2, D3],
+            Color::R => [R, R2, R3],
+            Color::L => [L, L2, L3],
+            Color::F => [F, F2, F3],
+            Color::B => [B, B2, B3],
+        };
+
+        match power % 4 {
+            0 => None,
+            p => Some(moves[p as usize - 1]),
+        }
+    }
 }
 
 pub fn is_move_available(prev: Move, current: Move) -> bool {
@@ -148,3 +186,36 @@ pub const B_MOVE: CubieCube = CubieCube {
     ep: [UB, DB, FR, FL, BR, UR, UF, UL, DF, DR, BL, DL],
     eo: [1, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0],
 };
+
+/// Cubie representation of the 18 moves, indexed by `Move as usize`.
+pub const MOVE_CUBES: [CubieCube; 18] = {
+    let faces = [U_MOVE, D_MOVE, R_MOVE, L_MOVE, F_MOVE, B_MOVE];
+    let mut cubes = [U_MOVE; 18];
+    let mut i = 0;
+
+    while i < 6 {
+        cubes[3 * i] = faces[i];
+        cubes[3 * i + 1] = multiply(&faces[i], &faces[i]);
+        cubes[3 * i + 2] = multiply(&cubes[3 * i + 1], &faces[i]);
+        i += 1;
+    }
+
+    cubes
+};
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::two_phase::utils::ALL_MOVES;
+
+    #[test]
+    fn test_move_cubes() {
+        for m in ALL_MOVES {
+            let power = m.get_power();
+            let quarter = MOVE_CUBES[m as usize + 1 - power as usize];
+            let expected = (1..power).fold(quarter, |acc, _| acc * quarter);
+
+            assert_eq!(MOVE_CUBES[m as usize], expected);
+        }
+    }
+}
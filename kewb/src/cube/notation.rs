@@ -0,0 +1,394 @@
+use std::{fmt, str::FromStr};
+
+use crate::error::Error;
+
+use super::{facelet::Color, moves::Move};
+
+/// Slice moves, M (follows L), E (follows D), S (follows F).
+/// $ clockwise, $2 double, $3 counter-clockwise.
+#[rustfmt::skip]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SliceMove {
+    M, M2, M3,
+    E, E2, E3,
+    S, S2, S3,
+}
+
+/// Whole cube rotations, x (follows R), y (follows U), z (follows F).
+/// $ clockwise, $2 double, $3 counter-clockwise.
+#[rustfmt::skip]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Rotation {
+    X, X2, X3,
+    Y, Y2, Y3,
+    Z, Z2, Z3,
+}
+
+/// Extended move notation: face turns, wide turns, slice moves and cube rotations.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExtMove {
+    /// Single layer face turn (R, U2, F').
+    Face(Move),
+    /// Two layers turn, the face is the outer layer (Rw, r, Uw2).
+    Wide(Move),
+    /// Middle layer turn (M, E2, S').
+    Slice(SliceMove),
+    /// Whole cube rotation (x, y2, z').
+    Rotation(Rotation),
+}
+
+fn power_suffix(power: u8) -> &'static str {
+    match power {
+        2 => "2",
+        3 => "'",
+        _ => "",
+    }
+}
+
+fn parse_power(s: &str) -> Option<u8> {
+    match s {
+        "" => Some(1),
+        "2" | "2'" => Some(2),
+        "'" => Some(3),
+        _ => None,
+    }
+}
+
+impl SliceMove {
+    /// Returns the number of quarter turns in the direction of the followed face: 1, 2 or 3.
+    pub fn get_power(&self) -> u8 {
+        *self as u8 % 3 + 1
+    }
+
+    pub fn get_inverse(self) -> Self {
+        use SliceMove::*;
+
+        match self {
+            M => M3,
+            M3 => M,
+            E => E3,
+            E3 => E,
+            S => S3,
+            S3 => S,
+            _ => self,
+        }
+    }
+
+    fn from_power(slice: char, power: u8) -> Option<Self> {
+        use SliceMove::*;
+
+        let moves = match slice {
+            'M' => [M, M2, M3],
+            'E' => [E, E2, E3],
+            'S' => [S, S2, S3],
+            _ => return None,
+        };
+
+        match power % 4 {
+            0 => None,
+            p => Some(moves[p as usize - 1]),
+        }
+    }
+}
+
+impl Rotation {
+    /// Returns the number of quarter turns in the direction of the followed face: 1, 2 or 3.
+    pub fn get_power(&self) -> u8 {
+        *self as u8 % 3 + 1
+    }
+
+    pub fn get_inverse(self) -> Self {
+        use Rotation::*;
+
+        match self {
+            X => X3,
+            X3 => X,
+            Y => Y3,
+            Y3 => Y,
+            Z => Z3,
+            Z3 => Z,
+            _ => self,
+        }
+    }
+
+    /// Builds the rotation turning the whole cube like `face` by `power` quarter turns.
+    /// Returns `None` when the power is a multiple of 4.
+    pub fn from_face(face: Color, power: u8) -> Option<Self> {
+        use Rotation::*;
+
+        let (rotations, power) = match face {
+            Color::R => ([X, X2, X3], power),
+            Color::L => ([X, X2, X3], 4 - power % 4),
+            Color::U => ([Y, Y2, Y3], power),
+            Color::D => ([Y, Y2, Y3], 4 - power % 4),
+            Color::F => ([Z, Z2, Z3], power),
+            Color::B => ([Z, Z2, Z3], 4 - power % 4),
+        };
+
+        match power % 4 {
+            0 => None,
+            p => Some(rotations[p as usize - 1]),
+        }
+    }
+}
+
+impl ExtMove {
+    pub fn get_inverse(self) -> Self {
+        match self {
+            ExtMove::Face(m) => ExtMove::Face(m.get_inverse()),
+            ExtMove::Wide(m) => ExtMove::Wide(m.get_inverse()),
+            ExtMove::Slice(m) => ExtMove::Slice(m.get_inverse()),
+            ExtMove::Rotation(r) => ExtMove::Rotation(r.get_inverse()),
+        }
+    }
+}
+
+impl From<Move> for ExtMove {
+    fn from(value: Move) -> Self {
+        ExtMove::Face(value)
+    }
+}
+
+impl fmt::Display for SliceMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = &format!("{:?}", self)[..1];
+        write!(f, "{name}{}", power_suffix(self.get_power()))
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!("{:?}", self)[..1].to_lowercase();
+        write!(f, "{name}{}", power_suffix(self.get_power()))
+    }
+}
+
+impl fmt::Display for ExtMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtMove::Face(m) => write!(f, "{m}"),
+            ExtMove::Wide(m) => {
+                let power = power_suffix(m.get_power());
+                write!(f, "{}w{power}", m.get_face())
+            }
+            ExtMove::Slice(m) => write!(f, "{m}"),
+            ExtMove::Rotation(r) => write!(f, "{r}"),
+        }
+    }
+}
+
+impl FromStr for ExtMove {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let first = chars.next().ok_or(Error::InvalidScramble)?;
+        let rest = chars.as_str();
+
+        let face = |c: char| Color::try_from(c.to_ascii_uppercase()).ok();
+
+        let result = match first {
+            'U' | 'D' | 'R' | 'L' | 'F' | 'B' => {
+                let face = face(first).ok_or(Error::InvalidScramble)?;
+
+                match rest.strip_prefix('w') {
+                    Some(rest) => parse_power(rest)
+                        .and_then(|p| Move::from_face(face, p))
+                        .map(ExtMove::Wide),
+                    None => parse_power(rest)
+                        .and_then(|p| Move::from_face(face, p))
+                        .map(ExtMove::Face),
+                }
+            }
+            'u' | 'd' | 'r' | 'l' | 'f' | 'b' => {
+                let face = face(first).ok_or(Error::InvalidScramble)?;
+                parse_power(rest)
+                    .and_then(|p| Move::from_face(face, p))
+                    .map(ExtMove::Wide)
+            }
+            'M' | 'E' | 'S' => {
+                parse_power(rest).and_then(|p| SliceMove::from_power(first, p).map(ExtMove::Slice))
+            }
+            'x' => parse_power(rest)
+                .and_then(|p| Rotation::from_face(Color::R, p))
+                .map(ExtMove::Rotation),
+            'y' => parse_power(rest)
+                .and_then(|p| Rotation::from_face(Color::U, p))
+                .map(ExtMove::Rotation),
+            'z' => parse_power(rest)
+                .and_then(|p| Rotation::from_face(Color::F, p))
+                .map(ExtMove::Rotation),
+            _ => None,
+        };
+
+        result.ok_or(Error::InvalidScramble)
+    }
+}
+
+/// Orientation of the whole cube, tracks which face of the initial orientation
+/// currently sits at each position. Indexed by the position, using the face order of `Color`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Orientation {
+    pub faces: [Color; 6],
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self {
+            faces: [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B],
+        }
+    }
+}
+
+impl Orientation {
+    /// Applies a cube rotation, the faces move in the direction of the followed face.
+    pub fn apply_rotation(self, rotation: Rotation) -> Self {
+        use Color::*;
+
+        // each cycle reads: the face at position i moves to position i + 1
+        let cycle = match rotation {
+            Rotation::X | Rotation::X2 | Rotation::X3 => [F, U, B, D],
+            Rotation::Y | Rotation::Y2 | Rotation::Y3 => [F, L, B, R],
+            Rotation::Z | Rotation::Z2 | Rotation::Z3 => [U, R, D, L],
+        };
+        let mut faces = self.faces;
+
+        for _ in 0..rotation.get_power() {
+            let previous = faces;
+            for i in 0..4 {
+                faces[cycle[(i + 1) % 4] as usize] = previous[cycle[i] as usize];
+            }
+        }
+
+        Self { faces }
+    }
+
+    /// Applies a sequence of cube rotations.
+    pub fn apply_rotations(self, rotations: &[Rotation]) -> Self {
+        rotations.iter().fold(self, |acc, &r| acc.apply_rotation(r))
+    }
+
+    /// Returns the face turn of the initial orientation matching a face turn in this orientation.
+    pub fn map_move(&self, m: Move) -> Move {
+        let face = self.faces[m.get_face() as usize];
+        Move::from_face(face, m.get_power()).unwrap()
+    }
+}
+
+fn opposite(face: Color) -> Color {
+    match face {
+        Color::U => Color::D,
+        Color::D => Color::U,
+        Color::R => Color::L,
+        Color::L => Color::R,
+        Color::F => Color::B,
+        Color::B => Color::F,
+    }
+}
+
+/// Converts extended moves into face turns, starting from the given orientation.
+/// Returns the face turns and the final orientation of the cube.
+pub fn expand_from(moves: &[ExtMove], orientation: Orientation) -> (Vec<Move>, Orientation) {
+    let mut orientation = orientation;
+    let mut result = Vec::new();
+
+    let mut push = |orientation: &Orientation, face: Color, power: u8| {
+        if let Some(m) = Move::from_face(face, power) {
+            result.push(orientation.map_move(m));
+        }
+    };
+
+    for m in moves {
+        match *m {
+            ExtMove::Face(m) => push(&orientation, m.get_face(), m.get_power()),
+            ExtMove::Wide(m) => {
+                // Rw = L x
+                let (face, power) = (m.get_face(), m.get_power());
+                push(&orientation, opposite(face), power);
+                orientation = orientation.apply_rotation(Rotation::from_face(face, power).unwrap());
+            }
+            ExtMove::Slice(m) => {
+                // M = R L' x', E = U D' y', S = F' B z
+                let power = m.get_power();
+                let (face, inverse) = match m {
+                    SliceMove::M | SliceMove::M2 | SliceMove::M3 => (Color::L, true),
+                    SliceMove::E | SliceMove::E2 | SliceMove::E3 => (Color::D, true),
+                    SliceMove::S | SliceMove::S2 | SliceMove::S3 => (Color::F, false),
+                };
+                let (first, first_power) = if inverse {
+                    (opposite(face), power)
+                } else {
+                    (face, 4 - power)
+                };
+                push(&orientation, first, first_power);
+                push(&orientation, opposite(first), 4 - first_power);
+                orientation = orientation.apply_rotation(Rotation::from_face(face, power).unwrap());
+            }
+            ExtMove::Rotation(r) => orientation = orientation.apply_rotation(r),
+        }
+    }
+
+    (result, orientation)
+}
+
+/// Converts extended moves into the face turns consumed by the solver.
+/// The state is kept relative to the centers, so the final cube rotation is dropped.
+pub fn expand(moves: &[ExtMove]) -> Vec<Move> {
+    expand_from(moves, Orientation::default()).0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cube::cubie::CubieCube, Move::*};
+
+    fn parse(s: &str) -> Vec<ExtMove> {
+        s.split_whitespace()
+            .map(|m| ExtMove::from_str(m).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_display() {
+        let moves = "R U2 F' Rw r' Uw2 Dw' M E2 S' x y2 z'";
+        let expected = "R U2 F' Rw Rw' Uw2 Dw' M E2 S' x y2 z'";
+        let stringified = parse(moves)
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        assert_eq!(stringified, expected);
+        assert!(ExtMove::from_str("Q").is_err());
+        assert!(ExtMove::from_str("Mw").is_err());
+        assert!(ExtMove::from_str("x3").is_err());
+    }
+
+    #[test]
+    fn test_rotations() {
+        assert_eq!(expand(&parse("x y z x2 y' z2")), vec![]);
+        assert_eq!(expand(&parse("x U")), vec![F]);
+        assert_eq!(expand(&parse("y R")), vec![B]);
+        assert_eq!(expand(&parse("z U")), vec![L]);
+        assert_eq!(expand(&parse("x y x' y'")), vec![]);
+        assert_eq!(
+            expand_from(&parse("x x'"), Orientation::default()).1,
+            Orientation::default()
+        );
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(expand(&parse("r U r'")), vec![L, F, L3]);
+        assert_eq!(expand(&parse("M' U")), vec![R3, L, F]);
+
+        let wide = CubieCube::from(&expand(&parse("Rw R'")));
+        let slice = CubieCube::from(&expand(&parse("M'")));
+        assert_eq!(wide, slice);
+
+        let h_perm = CubieCube::from(&expand(&parse("M2 U M2 U2 M2 U M2")));
+        let expected = CubieCube::from(&vec![R2, U2, R, U2, R2, U2, R2, U2, R, U2, R2]);
+        assert_eq!(h_perm, expected);
+    }
+}
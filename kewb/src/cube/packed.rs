@@ -0,0 +1,176 @@
+use super::{
+    cubie::{Corner, CubieCube, Edge},
+    moves::{Move, MOVE_CUBES},
+};
+
+/// Permutation and orientation change of one move on the packed pieces.
+#[derive(Clone, Copy)]
+struct PackedMove {
+    /// Position each piece comes from, edges are offset by 8.
+    source: [u8; 20],
+    /// Orientation added to each piece.
+    twist: [u8; 20],
+}
+
+const PACKED_MOVES: [PackedMove; 18] = {
+    let mut moves = [PackedMove {
+        source: [0; 20],
+        twist: [0; 20],
+    }; 18];
+    let mut m = 0;
+
+    while m < 18 {
+        let cube = &MOVE_CUBES[m];
+        let mut i = 0;
+
+        while i < 8 {
+            moves[m].source[i] = cube.cp[i] as u8;
+            moves[m].twist[i] = cube.co[i];
+            i += 1;
+        }
+
+        i = 0;
+
+        while i < 12 {
+            moves[m].source[8 + i] = 8 + cube.ep[i] as u8;
+            moves[m].twist[8 + i] = cube.eo[i];
+            i += 1;
+        }
+
+        m += 1;
+    }
+
+    moves
+};
+
+/// Packed corner after adding a twist, indexed by the twist and the packed corner.
+const CORNER_TWIST: [[u8; 24]; 3] = {
+    let mut table = [[0; 24]; 3];
+    let mut t = 0;
+
+    while t < 3 {
+        let mut c = 0;
+
+        while c < 24 {
+            table[t][c] = (c as u8 / 3) * 3 + (c as u8 % 3 + t as u8) % 3;
+            c += 1;
+        }
+
+        t += 1;
+    }
+
+    table
+};
+
+/// Cube stored as 20 bytes, one per piece, for fast move application.
+/// Corners are stored as `3 * piece + orientation`, edges as `2 * piece + orientation`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PackedCube {
+    pieces: [u8; 20],
+}
+
+/// Solved cube in packed form.
+pub const SOLVED_PACKED_CUBE: PackedCube = PackedCube {
+    pieces: [
+        0, 3, 6, 9, 12, 15, 18, 21, 0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22,
+    ],
+};
+
+impl Default for PackedCube {
+    fn default() -> Self {
+        SOLVED_PACKED_CUBE
+    }
+}
+
+impl From<&CubieCube> for PackedCube {
+    fn from(state: &CubieCube) -> Self {
+        let mut pieces = [0; 20];
+        let (corners, edges) = pieces.split_at_mut(8);
+
+        for (i, c) in corners.iter_mut().enumerate() {
+            *c = 3 * state.cp[i] as u8 + state.co[i];
+        }
+
+        for (i, e) in edges.iter_mut().enumerate() {
+            *e = 2 * state.ep[i] as u8 + state.eo[i];
+        }
+
+        Self { pieces }
+    }
+}
+
+impl From<PackedCube> for CubieCube {
+    fn from(packed: PackedCube) -> Self {
+        let mut state = CubieCube::default();
+
+        for i in 0..8 {
+            let c = packed.pieces[i];
+            state.cp[i] = Corner::try_from(c / 3).unwrap();
+            state.co[i] = c % 3;
+        }
+
+        for i in 0..12 {
+            let e = packed.pieces[8 + i];
+            state.ep[i] = Edge::try_from(e / 2).unwrap();
+            state.eo[i] = e % 2;
+        }
+
+        state
+    }
+}
+
+impl PackedCube {
+    pub fn apply_move(self, m: Move) -> Self {
+        let PackedMove { source, twist } = &PACKED_MOVES[m as usize];
+        let mut pieces = [0; 20];
+
+        for i in 0..8 {
+            pieces[i] = CORNER_TWIST[twist[i] as usize][self.pieces[source[i] as usize] as usize];
+        }
+
+        for i in 8..20 {
+            pieces[i] = self.pieces[source[i] as usize] ^ twist[i];
+        }
+
+        Self { pieces }
+    }
+
+    /// Applies the sequence of moves to the current state.
+    pub fn apply_moves(&self, moves: &[Move]) -> Self {
+        moves.iter().fold(*self, |acc, &m| acc.apply_move(m))
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == SOLVED_PACKED_CUBE
+    }
+
+    /// Returns the raw bytes, corners first then edges.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.pieces
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{generators::generate_random_state, two_phase::utils::ALL_MOVES, Move::*};
+
+    #[test]
+    fn test_packed_moves() {
+        let state = generate_random_state();
+        let packed = PackedCube::from(&state);
+
+        assert_eq!(CubieCube::from(packed), state);
+        assert!(PackedCube::default().is_solved());
+
+        for m in ALL_MOVES {
+            assert_eq!(CubieCube::from(packed.apply_move(m)), state.apply_move(m));
+        }
+
+        let scramble = vec![R, U, R3, U3, F2, D, L3, B];
+        assert_eq!(
+            CubieCube::from(PackedCube::default().apply_moves(&scramble)),
+            CubieCube::from(&scramble)
+        );
+    }
+}
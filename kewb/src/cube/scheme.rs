@@ -0,0 +1,160 @@
+use crate::error::Error;
+
+use super::{
+    facelet::{Color, FaceCube},
+    notation::Orientation,
+    transform::get_all_orientations,
+    utils::has_duplicates,
+};
+
+/// Maps the sticker colors of a facelet string to the faces of the cube.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ColorScheme {
+    /// Character of each face color, indexed by the face using the order of `Color`.
+    pub colors: [char; 6],
+}
+
+/// Uses the face names as colors: U, R, F, D, L, B.
+pub const FACE_SCHEME: ColorScheme = ColorScheme {
+    colors: ['U', 'R', 'F', 'D', 'L', 'B'],
+};
+
+/// Western color scheme, white top and green front: W, R, G, Y, O, B.
+pub const WESTERN_SCHEME: ColorScheme = ColorScheme {
+    colors: ['W', 'R', 'G', 'Y', 'O', 'B'],
+};
+
+/// Japanese color scheme, white top and green front: W, O, G, B, R, Y.
+pub const JAPANESE_SCHEME: ColorScheme = ColorScheme {
+    colors: ['W', 'O', 'G', 'B', 'R', 'Y'],
+};
+
+const FACES: [Color; 6] = [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B];
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        FACE_SCHEME
+    }
+}
+
+impl ColorScheme {
+    pub fn new(colors: [char; 6]) -> Result<Self, Error> {
+        if has_duplicates(&colors) {
+            return Err(Error::InvalidColorScheme);
+        }
+
+        Ok(Self { colors })
+    }
+
+    /// Infers the color scheme from the center stickers of a facelet string, so the string
+    /// can use any color alphabet and describe the cube held in any orientation.
+    pub fn from_centers(s: &str) -> Result<Self, Error> {
+        let chars = s.chars().collect::<Vec<_>>();
+
+        if chars.len() != 54 {
+            return Err(Error::InvalidFaceletString);
+        }
+
+        Self::new([4, 13, 22, 31, 40, 49].map(|i| chars[i]))
+    }
+
+    /// Returns the face having the given color.
+    pub fn get_face(&self, color: char) -> Option<Color> {
+        let i = self.colors.iter().position(|c| *c == color)?;
+        Some(FACES[i])
+    }
+
+    /// Returns the color of the given face.
+    pub fn get_color(&self, face: Color) -> char {
+        self.colors[face as usize]
+    }
+
+    /// Parses a facelet string written with the colors of the scheme.
+    pub fn parse(&self, s: &str) -> Result<FaceCube, Error> {
+        if s.chars().count() != 54 {
+            return Err(Error::InvalidFaceletString);
+        }
+
+        let mut face_cube = FaceCube::default();
+
+        for (i, c) in s.chars().enumerate() {
+            face_cube.f[i] = self.get_face(c).ok_or(Error::InvalidColor)?;
+        }
+
+        Ok(face_cube)
+    }
+
+    /// Writes the facelet string of a face cube with the colors of the scheme.
+    pub fn format(&self, face_cube: &FaceCube) -> String {
+        face_cube.f.iter().map(|f| self.get_color(*f)).collect()
+    }
+
+    /// Returns the orientation in which a cube colored with the reference scheme has these colors,
+    /// `None` if the scheme is not a rotation of the reference, a mirrored scheme for instance.
+    pub fn find_orientation(&self, reference: &ColorScheme) -> Option<Orientation> {
+        get_all_orientations().into_iter().find(|orientation| {
+            FACES
+                .iter()
+                .all(|f| self.get_color(*f) == reference.get_color(orientation.faces[*f as usize]))
+        })
+    }
+}
+
+impl FaceCube {
+    /// Parses a facelet string using any color alphabet, the faces are inferred from the centers.
+    pub fn from_colors(s: &str) -> Result<Self, Error> {
+        ColorScheme::from_centers(s)?.parse(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cube::notation::Rotation, CubieCube, Move::*};
+
+    #[test]
+    fn test_parse_format() {
+        let faces = "DRBLUURLDRBLRRBFLFFUBFFDRUDURRBDFBBULDUDLUDLBUFFDBFLRL";
+        let colors = "YRBOWWROYRBORRBGOGGWBGGYRWYWRRBYGBBWOYWYOWYOBWGGYBGORO";
+        let face_cube = FaceCube::try_from(faces).unwrap();
+
+        assert_eq!(WESTERN_SCHEME.format(&face_cube), colors);
+        assert_eq!(WESTERN_SCHEME.parse(colors).unwrap(), face_cube);
+        assert_eq!(FaceCube::from_colors(colors).unwrap(), face_cube);
+        assert!(WESTERN_SCHEME.parse(faces).is_err());
+    }
+
+    #[test]
+    fn test_orientation() {
+        // solved cube held with yellow on top and red in front
+        let colors = "YYYYYYYYYGGGGGGGGGRRRRRRRRRWWWWWWWWWBBBBBBBBBOOOOOOOOO";
+        let scheme = ColorScheme::from_centers(colors).unwrap();
+        let face_cube = FaceCube::from_colors(colors).unwrap();
+        let orientation = Orientation::default().apply_rotations(&[Rotation::X2, Rotation::Y]);
+
+        assert_eq!(
+            CubieCube::try_from(&face_cube).unwrap(),
+            CubieCube::default()
+        );
+        assert_eq!(scheme.find_orientation(&WESTERN_SCHEME), Some(orientation));
+
+        let mirrored = ColorScheme::new(['W', 'O', 'G', 'Y', 'R', 'B']).unwrap();
+        assert_eq!(mirrored.find_orientation(&WESTERN_SCHEME), None);
+        assert!(ColorScheme::new(['W', 'W', 'G', 'Y', 'R', 'B']).is_err());
+    }
+
+    #[test]
+    fn test_scrambled_orientation() {
+        // R U held with green on top and white in front
+        let state = CubieCube::from(&vec![R, U]);
+        let face_cube = FaceCube::try_from(&state).unwrap();
+        let orientation = Orientation::default().apply_rotation(Rotation::X);
+        let scheme = ColorScheme {
+            colors: orientation.faces.map(|f| WESTERN_SCHEME.get_color(f)),
+        };
+        let colors = scheme.format(&face_cube);
+
+        assert_eq!(&colors[4..5], "G");
+        assert_eq!(FaceCube::from_colors(&colors).unwrap(), face_cube);
+    }
+}
@@ -1,23 +1,17 @@
-use std::str::FromStr;
-
 use crate::{error::Error, CubieCube, Move, Solver};
 
+use super::{algorithm::algorithm_from_str, transform::invert};
+
+/// Parses a scramble written in the algorithm notation into face turns.
 pub fn scramble_from_str(s: &str) -> Result<Vec<Move>, Error> {
-    s.split_whitespace()
-        .map(|word| Move::from_str(word.trim()))
-        .collect()
+    algorithm_from_str(s)
 }
 
 pub fn scramble_from_state(state: CubieCube, solver: &mut Solver) -> Result<Vec<Move>, Error> {
     let solution = solver.solve(state);
 
     if let Some(solution) = solution {
-        Ok(solution
-            .get_all_moves()
-            .iter()
-            .rev()
-            .map(|m| m.get_inverse())
-            .collect())
+        Ok(invert(&solution.get_all_moves()))
     } else {
         Err(Error::InvalidCubieValue)
     }
@@ -0,0 +1,146 @@
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    algorithm::Algorithm,
+    cubie::{Corner, CubieCube, Edge},
+    facelet::{Color, FaceCube},
+    moves::Move,
+    notation::ExtMove,
+};
+
+/// Serializes a type as its string representation and parses it back.
+macro_rules! impl_string_serde {
+    ($t:ty, $parse:expr) => {
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                $parse(s.as_str()).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+fn parse_color(s: &str) -> Result<Color, crate::error::Error> {
+    let mut chars = s.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Color::try_from(c),
+        _ => Err(crate::error::Error::InvalidColor),
+    }
+}
+
+impl_string_serde!(Move, Move::from_str);
+impl_string_serde!(ExtMove, ExtMove::from_str);
+impl_string_serde!(Algorithm, Algorithm::from_str);
+impl_string_serde!(Color, parse_color);
+impl_string_serde!(FaceCube, FaceCube::try_from);
+
+/// Fields of a `CubieCube`. States are validated on both sides: serializing an unsolvable
+/// state fails, so every serialized state can be read back.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CubieFields {
+    cp: [Corner; 8],
+    co: [u8; 8],
+    ep: [Edge; 12],
+    eo: [u8; 12],
+}
+
+impl Serialize for CubieCube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.validate()
+            .into_result()
+            .map_err(serde::ser::Error::custom)?;
+
+        CubieFields {
+            cp: self.cp,
+            co: self.co,
+            ep: self.ep,
+            eo: self.eo,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl TryFrom<CubieFields> for CubieCube {
+    type Error = crate::error::Error;
+
+    fn try_from(value: CubieFields) -> Result<Self, Self::Error> {
+        let state = CubieCube {
+            cp: value.cp,
+            co: value.co,
+            ep: value.ep,
+            eo: value.eo,
+        };
+
+        state.validate().into_result()?;
+
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{supercube::SuperCube, CubieCube, FaceCube, Move::*, Solution};
+
+    #[test]
+    fn test_string_types() {
+        let moves = vec![R, U3, F2];
+        let face_cube = FaceCube::try_from(&CubieCube::from(&moves)).unwrap();
+        let json = serde_json::to_string(&face_cube).unwrap();
+
+        assert_eq!(serde_json::to_string(&moves).unwrap(), r#"["R","U'","F2"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<crate::Move>>(r#"["R","U'","F2"]"#).unwrap(),
+            moves
+        );
+        assert_eq!(json, format!("\"{face_cube}\""));
+        assert_eq!(serde_json::from_str::<FaceCube>(&json).unwrap(), face_cube);
+        assert!(serde_json::from_str::<crate::Move>(r#""Q""#).is_err());
+        assert!(serde_json::from_str::<crate::Color>(r#""UR""#).is_err());
+    }
+
+    #[test]
+    fn test_structured_types() {
+        let state = CubieCube::from(&vec![R, U]);
+        let json = serde_json::to_string(&state).unwrap();
+        let solution = Solution {
+            phase1: vec![R],
+            phase2: vec![U2, D3],
+        };
+
+        assert!(json.starts_with(r#"{"cp":["UFL","UBL","#));
+        assert_eq!(serde_json::from_str::<CubieCube>(&json).unwrap(), state);
+
+        // out of range orientations, duplicate pieces and twisted corners are rejected
+        let corrupted = [
+            json.replacen(r#""co":[0"#, r#""co":[7"#, 1),
+            json.replacen(r#""cp":["UFL","UBL""#, r#""cp":["UFL","UFL""#, 1),
+            json.replacen(r#""co":[0"#, r#""co":[1"#, 1),
+        ];
+
+        for json in corrupted {
+            assert!(serde_json::from_str::<CubieCube>(&json).is_err());
+        }
+
+        // unsolvable states are not serialized either, everything written can be read back
+        let mut twisted = state;
+        twisted.co[0] = (twisted.co[0] + 1) % 3;
+        assert!(serde_json::to_string(&twisted).is_err());
+        assert_eq!(
+            serde_json::to_string(&solution).unwrap(),
+            r#"{"phase1":["R"],"phase2":["U2","D'"]}"#
+        );
+
+        let cube = SuperCube::from(&vec![R, U]);
+        let json = serde_json::to_string(&cube).unwrap();
+        assert_eq!(serde_json::from_str::<SuperCube>(&json).unwrap(), cube);
+    }
+}
@@ -0,0 +1,93 @@
+use super::{facelet::Color, moves::Move};
+
+/// Faces of each axis, in the order used by the normal form.
+const AXIS_FACES: [[Color; 2]; 3] = [
+    [Color::U, Color::D],
+    [Color::R, Color::L],
+    [Color::F, Color::B],
+];
+
+/// Run of moves on the same axis, stores the quarter turns of each face.
+struct AxisGroup {
+    axis: usize,
+    powers: [u8; 2],
+}
+
+fn axis_side(m: Move) -> (usize, usize) {
+    let face = m as usize / 3;
+    (face / 2, face % 2)
+}
+
+/// Simplifies a move sequence into its normal form.
+/// Consecutive moves on the same axis commute: same face turns are merged and cancelled,
+/// then written in the U D, R L, F B order. Cancellations are applied until none are left,
+/// so two sequences differing only by such cancellations have the same normal form.
+pub fn simplify(moves: &[Move]) -> Vec<Move> {
+    let mut groups: Vec<AxisGroup> = Vec::new();
+
+    for m in moves {
+        let (axis, side) = axis_side(*m);
+
+        match groups.last_mut() {
+            Some(group) if group.axis == axis => {
+                group.powers[side] = (group.powers[side] + m.get_power()) % 4;
+
+                if group.powers == [0, 0] {
+                    groups.pop();
+                }
+            }
+            _ => {
+                let mut powers = [0, 0];
+                powers[side] = m.get_power();
+                groups.push(AxisGroup { axis, powers });
+            }
+        }
+    }
+
+    groups
+        .iter()
+        .flat_map(|group| {
+            let faces = AXIS_FACES[group.axis];
+            (0..2).filter_map(move |i| Move::from_face(faces[i], group.powers[i]))
+        })
+        .collect()
+}
+
+/// Checks if a move sequence is already in its normal form.
+pub fn is_simplified(moves: &[Move]) -> bool {
+    simplify(moves) == moves
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cube::cubie::CubieCube, Move::*};
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(simplify(&[R, R]), vec![R2]);
+        assert_eq!(simplify(&[R, R3]), vec![]);
+        assert_eq!(simplify(&[R2, R]), vec![R3]);
+        assert_eq!(simplify(&[R, U, U3, R3]), vec![]);
+        assert_eq!(simplify(&[F, R, U, U3, R2, F]), vec![F, R3, F]);
+    }
+
+    #[test]
+    fn test_opposite_faces() {
+        assert_eq!(simplify(&[R, L, R3]), vec![L]);
+        assert_eq!(simplify(&[L, R]), vec![R, L]);
+        assert_eq!(simplify(&[U, D, U, D3, D3]), vec![U2, D3]);
+        assert_eq!(simplify(&[R, L, U, U3, L3, R]), vec![R2]);
+        assert!(is_simplified(&[R, L, U, D3]));
+        assert!(!is_simplified(&[L, R]));
+    }
+
+    #[test]
+    fn test_same_state() {
+        let moves = vec![R, L, U, D, D3, U3, F, B3, F3, R2, L, U2, U2, B];
+        let simplified = simplify(&moves);
+
+        assert_eq!(simplified, vec![R, L, B3, R2, L, B]);
+        assert_eq!(CubieCube::from(&moves), CubieCube::from(&simplified));
+    }
+}
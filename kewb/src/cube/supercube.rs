@@ -0,0 +1,184 @@
+use super::{
+    cubie::CubieCube,
+    facelet::Color,
+    moves::Move,
+    transform::{get_all_orientations, reorient},
+};
+
+/// Turns the U center by 180° leaving the rest of the cube unchanged: (U R L U2 R' L')2.
+const U2_CENTER: [Move; 12] = {
+    use Move::*;
+    [U, R, L, U2, R3, L3, U, R, L, U2, R3, L3]
+};
+
+/// Turns the R center clockwise and the D center counterclockwise leaving the rest unchanged.
+const R_D3_CENTERS: [Move; 14] = {
+    use Move::*;
+    [U, D, R, U3, D3, F, B3, R, L, D3, L3, R3, B, F3]
+};
+
+const FACES: [Color; 6] = [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B];
+
+/// Cube with oriented centers, for picture cubes and supercubes.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuperCube {
+    pub state: CubieCube,
+    /// Clockwise quarter turns of each center, indexed by the face using the order of `Color`.
+    pub centers: [u8; 6],
+}
+
+impl From<CubieCube> for SuperCube {
+    fn from(state: CubieCube) -> Self {
+        Self {
+            state,
+            centers: [0; 6],
+        }
+    }
+}
+
+impl From<&Vec<Move>> for SuperCube {
+    fn from(moves: &Vec<Move>) -> Self {
+        SuperCube::default().apply_moves(moves)
+    }
+}
+
+impl SuperCube {
+    pub fn apply_move(self, m: Move) -> Self {
+        let mut centers = self.centers;
+        let face = m.get_face() as usize;
+        centers[face] = (centers[face] + m.get_power()) % 4;
+
+        Self {
+            state: self.state.apply_move(m),
+            centers,
+        }
+    }
+
+    pub fn apply_moves(&self, moves: &[Move]) -> Self {
+        moves.iter().fold(*self, |acc, &m| acc.apply_move(m))
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_solved() && self.centers.iter().all(|c| *c == 0)
+    }
+
+    /// Checks if the state is solvable, every quarter turn changes both the corner
+    /// permutation parity and the parity of the total center twist.
+    pub fn is_solvable(&self) -> bool {
+        let twist = self.centers.iter().map(|c| *c as u32).sum::<u32>();
+
+        self.state.is_solvable() && twist % 2 == self.state.count_corner_perm() as u32 % 2
+    }
+}
+
+/// Returns the pair algorithm turning `a` clockwise and `b` counterclockwise,
+/// `None` if the faces are opposite.
+fn center_pair(a: Color, b: Color) -> Option<Vec<Move>> {
+    get_all_orientations()
+        .iter()
+        .find(|o| o.faces[Color::R as usize] == a && o.faces[Color::D as usize] == b)
+        .map(|o| reorient(&R_D3_CENTERS, o))
+}
+
+fn half_center(face: Color) -> Vec<Move> {
+    let orientation = get_all_orientations()
+        .into_iter()
+        .find(|o| o.faces[Color::U as usize] == face)
+        .unwrap();
+
+    reorient(&U2_CENTER, &orientation)
+}
+
+/// Returns a sequence turning back the given center twists on an otherwise solved cube,
+/// `None` if the total twist is odd since no sequence can fix it.
+pub fn get_center_fix(centers: [u8; 6]) -> Option<Vec<Move>> {
+    // clockwise quarter turns still needed on each center
+    let mut needed = centers.map(|c| (4 - c % 4) % 4);
+
+    if needed.iter().sum::<u8>() % 2 != 0 {
+        return None;
+    }
+
+    let mut moves = Vec::new();
+
+    while let Some(a) = needed.iter().position(|c| c % 2 == 1) {
+        let b = (a + 1..6).find(|b| needed[*b] % 2 == 1).unwrap();
+        let (fa, fb) = (FACES[a], FACES[b]);
+        let pair = center_pair(fa, fb).unwrap_or_else(|| {
+            let via = FACES.into_iter().find(|c| *c != fa && *c != fb).unwrap();
+            let mut pair = center_pair(fa, via).unwrap();
+            pair.extend(center_pair(via, fb).unwrap());
+            pair
+        });
+
+        moves.extend(pair);
+        needed[a] = (needed[a] + 3) % 4;
+        needed[b] = (needed[b] + 1) % 4;
+    }
+
+    for (i, c) in needed.iter().enumerate() {
+        if *c == 2 {
+            moves.extend(half_center(FACES[i]));
+        }
+    }
+
+    Some(moves)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Move::*;
+
+    #[test]
+    fn test_center_algs() {
+        let u2 = SuperCube::from(&U2_CENTER.to_vec());
+        let pair = SuperCube::from(&R_D3_CENTERS.to_vec());
+
+        assert!(u2.state.is_solved());
+        assert_eq!(u2.centers, [2, 0, 0, 0, 0, 0]);
+        assert!(pair.state.is_solved());
+        assert_eq!(pair.centers, [0, 1, 0, 3, 0, 0]);
+    }
+
+    #[test]
+    fn test_solvable() {
+        let cube = SuperCube::from(&vec![R, U, R3, U3]);
+
+        assert!(cube.is_solvable());
+        assert_eq!(cube.centers, [0, 0, 0, 0, 0, 0]);
+
+        let mut twisted = SuperCube::default();
+        twisted.centers[0] = 1;
+        assert!(!twisted.is_solvable());
+
+        twisted.centers[3] = 1;
+        assert!(twisted.is_solvable());
+        assert!(!twisted.is_solved());
+    }
+
+    #[test]
+    fn test_center_fix() {
+        let twists = [
+            [1, 0, 0, 3, 0, 0],
+            [2, 0, 0, 0, 0, 0],
+            [1, 1, 1, 1, 0, 0],
+            [3, 2, 1, 0, 3, 1],
+            [0, 0, 0, 0, 1, 1],
+        ];
+
+        for centers in twists {
+            let cube = SuperCube {
+                state: CubieCube::default(),
+                centers,
+            };
+            let fix = get_center_fix(centers).unwrap();
+
+            assert!(cube.apply_moves(&fix).is_solved());
+        }
+
+        assert!(get_center_fix([1, 0, 0, 0, 0, 0]).is_none());
+        assert!(get_center_fix([0; 6]).unwrap().is_empty());
+    }
+}
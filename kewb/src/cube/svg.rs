@@ -0,0 +1,370 @@
+use std::fmt::Write;
+
+use crate::error::Error;
+
+use super::{facelet::FaceCube, symmetry::facelet_geometry};
+
+/// Views in which a cube can be rendered.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum View {
+    /// Unfolded net with U on top, then L, F, R, B and D at the bottom.
+    Net,
+    /// 3D view showing the U, F and R faces.
+    Isometric,
+    /// U face surrounded by the top row of the side faces, used for OLL and PLL cards.
+    TopLayer,
+}
+
+/// Arrow drawn over the U face, stickers are numbered from 0 to 8 in reading order.
+/// Arrows are drawn in the net and top layer views.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Arrow {
+    pub from: usize,
+    pub to: usize,
+    /// Draws a head at both ends, for swaps.
+    pub double: bool,
+}
+
+impl Arrow {
+    pub fn new(from: usize, to: usize) -> Self {
+        Self {
+            from,
+            to,
+            double: false,
+        }
+    }
+
+    pub fn swap(from: usize, to: usize) -> Self {
+        Self {
+            from,
+            to,
+            double: true,
+        }
+    }
+}
+
+/// Rendering options, colors can be any SVG color.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SvgOptions {
+    pub view: View,
+    /// Fill color of each face, indexed by the face using the order of `Color`.
+    pub colors: [String; 6],
+    pub border_color: String,
+    pub arrow_color: String,
+    /// Side length of a sticker.
+    pub sticker_size: f32,
+    pub arrows: Vec<Arrow>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            view: View::Net,
+            colors: [
+                "#ffffff", "#b71234", "#009b48", "#ffd500", "#ff5800", "#0046ad",
+            ]
+            .map(String::from),
+            border_color: "#000000".to_owned(),
+            arrow_color: "#000000".to_owned(),
+            sticker_size: 30.0,
+            arrows: Vec::new(),
+        }
+    }
+}
+
+/// Position of the faces in the net, in stickers, using the order of `Color`.
+const NET_POSITIONS: [(f32, f32); 6] = [
+    (3.0, 0.0),
+    (6.0, 3.0),
+    (3.0, 3.0),
+    (3.0, 6.0),
+    (0.0, 3.0),
+    (9.0, 3.0),
+];
+
+const COS_30: f32 = 0.866_025_4;
+
+/// Escapes a user supplied value written in an attribute.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+struct Canvas<'a> {
+    options: &'a SvgOptions,
+    body: String,
+}
+
+impl<'a> Canvas<'a> {
+    fn new(options: &'a SvgOptions) -> Self {
+        Self {
+            options,
+            body: String::new(),
+        }
+    }
+
+    fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, fill: &str) {
+        let _ = writeln!(
+            self.body,
+            r#"<rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{height:.2}" fill="{}" stroke="{}" stroke-width="{:.2}"/>"#,
+            escape(fill),
+            escape(&self.options.border_color),
+            self.options.sticker_size / 16.0,
+        );
+    }
+
+    fn polygon(&mut self, points: &[(f32, f32)], fill: &str) {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{x:.2},{y:.2}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let _ = writeln!(
+            self.body,
+            r#"<polygon points="{points}" fill="{}" stroke="{}" stroke-width="{:.2}"/>"#,
+            escape(fill),
+            escape(&self.options.border_color),
+            self.options.sticker_size / 16.0,
+        );
+    }
+
+    /// Draws the arrows over the U face whose top left corner is at the given position.
+    fn arrows(&mut self, x: f32, y: f32) {
+        let size = self.options.sticker_size;
+        let center = |k: usize| {
+            (
+                x + (k % 3) as f32 * size + size / 2.0,
+                y + (k / 3) as f32 * size + size / 2.0,
+            )
+        };
+
+        for arrow in &self.options.arrows {
+            let (x1, y1) = center(arrow.from);
+            let (x2, y2) = center(arrow.to);
+            let start = if arrow.double {
+                r#" marker-start="url(#arrowhead)""#
+            } else {
+                ""
+            };
+
+            let _ = writeln!(
+                self.body,
+                r#"<line x1="{x1:.2}" y1="{y1:.2}" x2="{x2:.2}" y2="{y2:.2}" stroke="{}" stroke-width="{:.2}" marker-end="url(#arrowhead)"{start}/>"#,
+                escape(&self.options.arrow_color),
+                size / 10.0,
+            );
+        }
+    }
+
+    fn finish(self, width: f32, height: f32) -> String {
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.2}" height="{height:.2}" viewBox="0 0 {width:.2} {height:.2}">"#
+        );
+        svg.push('\n');
+
+        if !self.options.arrows.is_empty() && self.options.view != View::Isometric {
+            let _ = writeln!(
+                svg,
+                r#"<defs><marker id="arrowhead" viewBox="0 0 10 10" refX="8" refY="5" markerWidth="4" markerHeight="4" orient="auto-start-reverse"><path d="M0,0L10,5L0,10z" fill="{}"/></marker></defs>"#,
+                escape(&self.options.arrow_color)
+            );
+        }
+
+        svg.push_str(&self.body);
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn render_net(face_cube: &FaceCube, options: &SvgOptions) -> String {
+    let size = options.sticker_size;
+    let margin = size / 10.0;
+    let mut canvas = Canvas::new(options);
+
+    for (i, (fx, fy)) in NET_POSITIONS.iter().enumerate() {
+        for k in 0..9 {
+            let x = margin + (fx + (k % 3) as f32) * size;
+            let y = margin + (fy + (k / 3) as f32) * size;
+            let color = face_cube.f[9 * i + k] as usize;
+            canvas.rect(x, y, size, size, &options.colors[color]);
+        }
+    }
+
+    canvas.arrows(margin + 3.0 * size, margin);
+    canvas.finish(12.0 * size + 2.0 * margin, 9.0 * size + 2.0 * margin)
+}
+
+fn render_top_layer(face_cube: &FaceCube, options: &SvgOptions) -> String {
+    let size = options.sticker_size;
+    let margin = size / 10.0;
+    let thickness = size / 3.0;
+    let gap = size / 10.0;
+    let origin = margin + thickness + gap;
+    let far = origin + 3.0 * size + gap;
+    let color = |f: usize| options.colors[face_cube.f[f] as usize].as_str();
+    let mut canvas = Canvas::new(options);
+
+    for k in 0..9 {
+        let x = origin + (k % 3) as f32 * size;
+        let y = origin + (k / 3) as f32 * size;
+        canvas.rect(x, y, size, size, color(k));
+    }
+
+    for k in 0..3 {
+        let offset = origin + k as f32 * size;
+        // the side stickers are listed from their left when facing the side
+        canvas.rect(offset, margin, size, thickness, color(47 - k));
+        canvas.rect(offset, far, size, thickness, color(18 + k));
+        canvas.rect(margin, offset, thickness, size, color(36 + k));
+        canvas.rect(far, offset, thickness, size, color(11 - k));
+    }
+
+    canvas.arrows(origin, origin);
+
+    let length = far + thickness + margin;
+    canvas.finish(length, length)
+}
+
+fn render_isometric(face_cube: &FaceCube, options: &SvgOptions) -> String {
+    let size = options.sticker_size;
+    let margin = size / 10.0;
+    let width = 6.0 * COS_30 * size + 2.0 * margin;
+    let height = 6.0 * size + 2.0 * margin;
+    let project = |p: [f32; 3]| {
+        (
+            width / 2.0 + (p[0] - p[2]) * COS_30 * size,
+            height / 2.0 + ((p[0] + p[2]) / 2.0 - p[1]) * size,
+        )
+    };
+    // corners of the square of the given half size centered on the point, orthogonal to the normal
+    let square = |center: [f32; 3], normal: [i8; 3], half: f32| {
+        let axes = (0..3).filter(|a| normal[*a] == 0).collect::<Vec<_>>();
+
+        [(1.0, 1.0), (1.0, -1.0), (-1.0, -1.0), (-1.0, 1.0)].map(|(a, b)| {
+            let mut p = center;
+            p[axes[0]] += a * half;
+            p[axes[1]] += b * half;
+            project(p)
+        })
+    };
+    let mut canvas = Canvas::new(options);
+
+    // U, R and F are the first three faces
+    for face in 0..3 {
+        let (_, normal) = facelet_geometry(9 * face + 4);
+        let center = normal.map(|n| n as f32 * 1.5);
+        canvas.polygon(&square(center, normal, 1.5), &options.border_color);
+
+        for k in 0..9 {
+            let facelet = 9 * face + k;
+            let (position, normal) = facelet_geometry(facelet);
+            let center = [0, 1, 2].map(|a| position[a] as f32 + normal[a] as f32 / 2.0);
+            let color = face_cube.f[facelet] as usize;
+            canvas.polygon(&square(center, normal, 0.45), &options.colors[color]);
+        }
+    }
+
+    canvas.finish(width, height)
+}
+
+impl FaceCube {
+    /// Renders the cube as an SVG image, fails if an arrow uses a sticker outside the U face.
+    pub fn to_svg(&self, options: &SvgOptions) -> Result<String, Error> {
+        let invalid = options
+            .arrows
+            .iter()
+            .flat_map(|arrow| [arrow.from, arrow.to])
+            .find(|sticker| *sticker > 8);
+
+        if let Some(sticker) = invalid {
+            return Err(Error::InvalidArrow(sticker));
+        }
+
+        Ok(match options.view {
+            View::Net => render_net(self, options),
+            View::Isometric => render_isometric(self, options),
+            View::TopLayer => render_top_layer(self, options),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CubieCube, Move::*};
+
+    #[test]
+    fn test_net() {
+        let face_cube = FaceCube::try_from(&CubieCube::from(&vec![R, U])).unwrap();
+        let svg = face_cube.to_svg(&SvgOptions::default()).unwrap();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 54);
+        assert_eq!(svg.matches("#ffffff").count(), 9);
+        assert!(!svg.contains("<line"));
+    }
+
+    #[test]
+    fn test_top_layer() {
+        let options = SvgOptions {
+            view: View::TopLayer,
+            arrows: vec![Arrow::swap(1, 5), Arrow::new(3, 7)],
+            colors: ["U", "R", "F", "D", "L", "B"].map(String::from),
+            ..Default::default()
+        };
+        // T perm swaps UL and UR, UFR and UBR
+        let t_perm = vec![R, U, R3, U3, R3, F, R2, U3, R3, U3, R, U, R3, F3];
+        let face_cube = FaceCube::try_from(&CubieCube::from(&t_perm)).unwrap();
+        let svg = face_cube.to_svg(&options).unwrap();
+
+        assert_eq!(svg.matches("<rect").count(), 21);
+        assert_eq!(svg.matches("<line").count(), 2);
+        assert_eq!(svg.matches("marker-start").count(), 1);
+        assert_eq!(svg.matches(r#"fill="U""#).count(), 9);
+        assert_eq!(svg.matches(r#"fill="R""#).count(), 3);
+        assert!(svg.contains("<defs>"));
+    }
+
+    #[test]
+    fn test_isometric() {
+        let options = SvgOptions {
+            view: View::Isometric,
+            arrows: vec![Arrow::swap(1, 5)],
+            ..Default::default()
+        };
+        let svg = FaceCube::default().to_svg(&options).unwrap();
+
+        assert_eq!(svg.matches("<polygon").count(), 30);
+        assert_eq!(svg.matches("#ffd500").count(), 0);
+        assert!(!svg.contains("<line"));
+        assert!(!svg.contains("<defs>"));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let options = SvgOptions {
+            colors: [r#"red"/><script/>"#, "R", "F", "D", "L", "B"].map(String::from),
+            ..Default::default()
+        };
+        let svg = FaceCube::default().to_svg(&options).unwrap();
+
+        assert!(!svg.contains("<script"));
+        assert_eq!(svg.matches("red&quot;/&gt;&lt;script/&gt;").count(), 9);
+
+        let options = SvgOptions {
+            arrows: vec![Arrow::new(1, 54)],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            FaceCube::default().to_svg(&options),
+            Err(Error::InvalidArrow(54))
+        ));
+    }
+}
@@ -0,0 +1,264 @@
+use std::sync::OnceLock;
+
+use super::{
+    cubie::{Corner, CubieCube, Edge},
+    facelet::{CORNER_FACELET, EDGE_FACELET},
+};
+
+/// The total count of cube symmetries, rotations and reflections.
+pub const SYM_COUNT: usize = 48;
+
+type Matrix = [[i8; 3]; 3];
+
+/// 120° rotation around the URF-DBL axis: U goes to R, R goes to F, F goes to U.
+const URF3_MATRIX: Matrix = [[0, 1, 0], [0, 0, 1], [1, 0, 0]];
+/// 180° rotation around the F-B axis.
+const F2_MATRIX: Matrix = [[-1, 0, 0], [0, -1, 0], [0, 0, 1]];
+/// 90° rotation around the U-D axis, same direction as U.
+const U4_MATRIX: Matrix = [[0, 0, -1], [0, 1, 0], [1, 0, 0]];
+/// Reflection swapping the L and R faces.
+const LR2_MATRIX: Matrix = [[-1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+/// Returns the position of the cubie holding the facelet and the normal of its face,
+/// with x pointing to R, y pointing to U and z pointing to F.
+pub(super) fn facelet_geometry(facelet: usize) -> ([i8; 3], [i8; 3]) {
+    let r = (facelet % 9 / 3) as i8;
+    let c = (facelet % 3) as i8;
+
+    match facelet / 9 {
+        0 => ([c - 1, 1, r - 1], [0, 1, 0]),
+        1 => ([1, 1 - r, 1 - c], [1, 0, 0]),
+        2 => ([c - 1, 1 - r, 1], [0, 0, 1]),
+        3 => ([c - 1, -1, 1 - r], [0, -1, 0]),
+        4 => ([-1, 1 - r, c - 1], [-1, 0, 0]),
+        _ => ([1 - c, 1 - r, -1], [0, 0, -1]),
+    }
+}
+
+fn transform(matrix: &Matrix, v: [i8; 3]) -> [i8; 3] {
+    matrix.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+fn determinant(m: &Matrix) -> i8 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Returns the facelet where the given facelet is sent by the transformation.
+fn transform_facelet(matrix: &Matrix, facelet: usize) -> usize {
+    let (position, normal) = facelet_geometry(facelet);
+    let target = (transform(matrix, position), transform(matrix, normal));
+
+    (0..54).find(|&f| facelet_geometry(f) == target).unwrap()
+}
+
+/// Builds the cubie representation of a rotation or reflection of the whole cube.
+/// Reflections use the corner orientations 3, 4 and 5.
+fn symmetry_from_matrix(matrix: &Matrix) -> CubieCube {
+    let mut state = CubieCube::default();
+    let is_mirror = determinant(matrix) < 0;
+
+    for (j, facelets) in CORNER_FACELET.iter().enumerate() {
+        let target = transform_facelet(matrix, facelets[0] as usize);
+
+        for (i, slot) in CORNER_FACELET.iter().enumerate() {
+            if let Some(ori) = slot.iter().position(|f| *f as usize == target) {
+                state.cp[i] = Corner::try_from(j as u8).unwrap();
+                state.co[i] = if is_mirror {
+                    3 + (3 - ori as u8) % 3
+                } else {
+                    ori as u8
+                };
+            }
+        }
+    }
+
+    for (j, facelets) in EDGE_FACELET.iter().enumerate() {
+        let target = transform_facelet(matrix, facelets[0] as usize);
+
+        for (i, slot) in EDGE_FACELET.iter().enumerate() {
+            if let Some(ori) = slot.iter().position(|f| *f as usize == target) {
+                state.ep[i] = Edge::try_from(j as u8).unwrap();
+                state.eo[i] = ori as u8;
+            }
+        }
+    }
+
+    state
+}
+
+struct Symmetries {
+    cubes: Vec<CubieCube>,
+    inverse: Vec<usize>,
+}
+
+fn symmetries() -> &'static Symmetries {
+    static SYMMETRIES: OnceLock<Symmetries> = OnceLock::new();
+
+    SYMMETRIES.get_or_init(|| {
+        let urf3 = symmetry_from_matrix(&URF3_MATRIX);
+        let f2 = symmetry_from_matrix(&F2_MATRIX);
+        let u4 = symmetry_from_matrix(&U4_MATRIX);
+        let lr2 = symmetry_from_matrix(&LR2_MATRIX);
+        let mut cubes = Vec::with_capacity(SYM_COUNT);
+        let mut state = CubieCube::default();
+
+        // index = 16 * urf3 + 8 * f2 + 2 * u4 + lr2
+        for _ in 0..3 {
+            for _ in 0..2 {
+                for _ in 0..4 {
+                    for _ in 0..2 {
+                        cubes.push(state);
+                        state = state * lr2;
+                    }
+                    state = state * u4;
+                }
+                state = state * f2;
+            }
+            state = state * urf3;
+        }
+
+        let inverse = cubes
+            .iter()
+            .map(|a| {
+                cubes
+                    .iter()
+                    .position(|b| *a * *b == CubieCube::default())
+                    .unwrap()
+            })
+            .collect();
+
+        Symmetries { cubes, inverse }
+    })
+}
+
+/// Returns the cubie representation of the symmetry.
+/// Symmetries are indexed by `16 * urf3 + 8 * f2 + 2 * u4 + lr2`, so the first 16 preserve the U-D axis.
+///
+/// # Panics
+///
+/// Panics if `index` is not lower than `SYM_COUNT`.
+pub fn get_symmetry(index: usize) -> CubieCube {
+    debug_assert!(index < SYM_COUNT, "symmetry index {index} out of range");
+    symmetries().cubes[index]
+}
+
+/// Returns the index of the inverse symmetry.
+///
+/// # Panics
+///
+/// Panics if `index` is not lower than `SYM_COUNT`.
+pub fn get_inverse_symmetry(index: usize) -> usize {
+    debug_assert!(index < SYM_COUNT, "symmetry index {index} out of range");
+    symmetries().inverse[index]
+}
+
+fn sort_key(state: &CubieCube) -> ([u8; 8], [u8; 8], [u8; 12], [u8; 12]) {
+    (
+        state.cp.map(|c| c as u8),
+        state.co,
+        state.ep.map(|e| e as u8),
+        state.eo,
+    )
+}
+
+impl CubieCube {
+    /// Returns the conjugate S * state * S⁻¹ by the symmetry with the given index.
+    pub fn conjugate(&self, symmetry: usize) -> Self {
+        get_symmetry(symmetry) * *self * get_symmetry(get_inverse_symmetry(symmetry))
+    }
+
+    /// Returns the representative of the symmetry class and the index of the symmetry
+    /// giving it by conjugation. Symmetric states share the same representative.
+    pub fn symmetry_representative(&self) -> (Self, usize) {
+        (0..SYM_COUNT)
+            .map(|s| (self.conjugate(s), s))
+            .min_by_key(|(state, _)| sort_key(state))
+            .unwrap()
+    }
+
+    /// Returns the indices of the symmetries leaving the state unchanged by conjugation.
+    pub fn get_self_symmetries(&self) -> Vec<usize> {
+        (0..SYM_COUNT)
+            .filter(|&s| self.conjugate(s) == *self)
+            .collect()
+    }
+
+    /// Checks if two states are equal up to a symmetry of the cube.
+    pub fn is_symmetric_to(&self, other: &Self) -> bool {
+        self.symmetry_representative().0 == other.symmetry_representative().0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cube::{cubie::SOLVED_CUBIE_CUBE, moves::*};
+
+    #[test]
+    fn test_group() {
+        let cubes = &symmetries().cubes;
+
+        assert_eq!(cubes[0], SOLVED_CUBIE_CUBE);
+
+        for (i, a) in cubes.iter().enumerate() {
+            assert!(cubes[i + 1..].iter().all(|b| a != b));
+            assert!(cubes.iter().all(|b| cubes.contains(&(*a * *b))));
+            assert_eq!(
+                *a * get_symmetry(get_inverse_symmetry(i)),
+                SOLVED_CUBIE_CUBE
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_symmetry_out_of_range() {
+        get_symmetry(SYM_COUNT);
+    }
+
+    #[test]
+    fn test_conjugate_moves() {
+        let u4 = 2;
+        let lr2 = 1;
+
+        assert_eq!(CubieCube::from(&vec![Move::R]).conjugate(u4), B_MOVE);
+        assert_eq!(CubieCube::from(&vec![Move::U]).conjugate(u4), U_MOVE);
+        assert_eq!(
+            CubieCube::from(&vec![Move::R]).conjugate(lr2),
+            CubieCube::from(&vec![Move::L3])
+        );
+
+        let moves = [Move::U, Move::R, Move::F, Move::D, Move::L, Move::B]
+            .map(|m| CubieCube::from(&vec![m]))
+            .into_iter()
+            .flat_map(|m| [m, m * m, m * m * m])
+            .collect::<Vec<_>>();
+
+        for s in 0..SYM_COUNT {
+            for m in &moves {
+                assert!(moves.contains(&m.conjugate(s)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_representative() {
+        let state = CubieCube::from(&vec![Move::R, Move::U, Move::R3, Move::U3]);
+        let mirrored = CubieCube::from(&vec![Move::L3, Move::U3, Move::L, Move::U]);
+        let rotated = CubieCube::from(&vec![Move::F, Move::U, Move::F3, Move::U3]);
+        let other = CubieCube::from(&vec![Move::R, Move::U, Move::R3, Move::U]);
+        let (representative, s) = state.symmetry_representative();
+
+        assert_eq!(state.conjugate(s), representative);
+        assert!(state.is_symmetric_to(&mirrored));
+        assert!(state.is_symmetric_to(&rotated));
+        assert!(!state.is_symmetric_to(&other));
+        assert_eq!(SOLVED_CUBIE_CUBE.get_self_symmetries().len(), SYM_COUNT);
+        assert_eq!(
+            CubieCube::from(&vec![Move::U]).get_self_symmetries(),
+            vec![0, 2, 4, 6]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use super::{
+    facelet::Color,
+    moves::Move,
+    notation::{ExtMove, Orientation, Rotation},
+};
+
+/// Planes used for mirroring move sequences.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Mirror {
+    /// Swaps the left and right faces.
+    LeftRight,
+    /// Swaps the front and back faces.
+    FrontBack,
+    /// Swaps the up and down faces.
+    UpDown,
+}
+
+/// Moves that can be undone by another move.
+pub trait Invert: Copy {
+    fn get_inverse(self) -> Self;
+}
+
+impl Invert for Move {
+    fn get_inverse(self) -> Self {
+        Move::get_inverse(self)
+    }
+}
+
+impl Invert for ExtMove {
+    fn get_inverse(self) -> Self {
+        ExtMove::get_inverse(self)
+    }
+}
+
+/// Returns the inverse of a move sequence.
+pub fn invert<M: Invert>(moves: &[M]) -> Vec<M> {
+    moves.iter().rev().map(|m| m.get_inverse()).collect()
+}
+
+/// Returns the mirror image of a move sequence, every move turns in the opposite direction.
+pub fn mirror(moves: &[Move], plane: Mirror) -> Vec<Move> {
+    let swapped = match plane {
+        Mirror::LeftRight => [Color::L, Color::R],
+        Mirror::FrontBack => [Color::F, Color::B],
+        Mirror::UpDown => [Color::U, Color::D],
+    };
+
+    moves
+        .iter()
+        .map(|m| {
+            let face = match m.get_face() {
+                f if f == swapped[0] => swapped[1],
+                f if f == swapped[1] => swapped[0],
+                f => f,
+            };
+
+            Move::from_face(face, 4 - m.get_power()).unwrap()
+        })
+        .collect()
+}
+
+/// Returns the image of a move sequence performed while holding the cube in the given orientation.
+/// Example: the orientation after `y'` turns an R-based algorithm into an F-based one.
+pub fn reorient(moves: &[Move], orientation: &Orientation) -> Vec<Move> {
+    moves.iter().map(|m| orientation.map_move(*m)).collect()
+}
+
+/// Returns the 24 orientations of the cube, every face on top followed by the 4 `y` rotations.
+pub fn get_all_orientations() -> Vec<Orientation> {
+    use Rotation::*;
+
+    let tops: [&[Rotation]; 6] = [&[], &[X], &[X2], &[X3], &[Z], &[Z3]];
+
+    tops.iter()
+        .flat_map(|top| {
+            let orientation = Orientation::default().apply_rotations(top);
+
+            (0..4).scan(orientation, |orientation, _| {
+                let current = *orientation;
+                *orientation = orientation.apply_rotation(Y);
+                Some(current)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cube::cubie::CubieCube, Move::*};
+
+    #[test]
+    fn test_invert() {
+        let moves = vec![R, U, R3, U3, F2];
+        let state = CubieCube::from(&moves).apply_moves(&invert(&moves));
+
+        assert_eq!(invert(&moves), vec![F2, U, R, U3, R3]);
+        assert_eq!(state, CubieCube::default());
+    }
+
+    #[test]
+    fn test_mirror() {
+        let sexy = [R, U, R3, U3];
+
+        assert_eq!(mirror(&sexy, Mirror::LeftRight), vec![L3, U3, L, U]);
+        assert_eq!(mirror(&sexy, Mirror::FrontBack), vec![R3, U3, R, U]);
+        assert_eq!(mirror(&sexy, Mirror::UpDown), vec![R3, D3, R, D]);
+        assert_eq!(
+            mirror(&mirror(&sexy, Mirror::LeftRight), Mirror::LeftRight),
+            sexy
+        );
+    }
+
+    #[test]
+    fn test_reorient() {
+        let sexy = [R, U, R3, U3];
+        let orientations = get_all_orientations();
+        let y3 = Orientation::default().apply_rotation(Rotation::Y3);
+        let z = Orientation::default().apply_rotation(Rotation::Z);
+
+        assert_eq!(reorient(&sexy, &y3), vec![F, U, F3, U3]);
+        assert_eq!(reorient(&sexy, &z), vec![U, L, U3, L3]);
+        assert_eq!(orientations.len(), 24);
+        assert_eq!(orientations[0], Orientation::default());
+
+        for (i, a) in orientations.iter().enumerate() {
+            assert!(orientations[i + 1..].iter().all(|b| a != b));
+        }
+    }
+}
@@ -0,0 +1,441 @@
+use std::fmt;
+
+use crate::error::Error;
+
+use super::{
+    cubie::{Corner, CubieCube, Edge, SOLVED_CUBIE_CUBE},
+    facelet::{Color, FaceCube, CORNER_COLOR, CORNER_FACELET, EDGE_COLOR, EDGE_FACELET},
+};
+
+const SOLVED_COLORS: [Color; 6] = [Color::U, Color::R, Color::F, Color::D, Color::L, Color::B];
+
+/// Reason making a cube state invalid, facelets are given by their index in `FaceCube::f`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Issue {
+    /// A color does not appear exactly 9 times.
+    ColorCount { color: Color, count: usize },
+    /// A center does not have the color of its face.
+    WrongCenter { facelet: usize, color: Color },
+    /// The stickers at a corner position do not form an existing corner.
+    InvalidCornerColors {
+        position: Corner,
+        facelets: [usize; 3],
+    },
+    /// The stickers at an edge position do not form an existing edge.
+    InvalidEdgeColors {
+        position: Edge,
+        facelets: [usize; 2],
+    },
+    /// A corner orientation is out of range.
+    InvalidCornerOrientation { position: Corner },
+    /// An edge orientation is out of range.
+    InvalidEdgeOrientation { position: Edge },
+    /// The same corner appears at several positions.
+    DuplicateCorner {
+        corner: Corner,
+        positions: Vec<Corner>,
+    },
+    /// The same edge appears at several positions.
+    DuplicateEdge { edge: Edge, positions: Vec<Edge> },
+    /// A corner does not appear in the cube.
+    MissingCorner(Corner),
+    /// An edge does not appear in the cube.
+    MissingEdge(Edge),
+    /// The corners are twisted by the given amount, a single corner twisted in place for instance.
+    /// The position is the last twisted corner, its orientation does not fit the other corners.
+    TwistedCorner { position: Corner, twist: u8 },
+    /// A single edge is flipped, the position is the last flipped edge.
+    FlippedEdge { position: Edge },
+    /// The corner and edge permutations have different parities, two pieces are swapped.
+    /// The positions are the last misplaced corner and the home of its piece,
+    /// swapping them fixes the parity.
+    CornerParity { positions: [Corner; 2] },
+    /// Same as `CornerParity` for the edges, reported when an edge is misplaced.
+    EdgeParity { positions: [Edge; 2] },
+}
+
+impl From<&Issue> for Error {
+    fn from(issue: &Issue) -> Self {
+        match issue {
+            Issue::ColorCount { color, count } => Error::InvalidColorCount(*color, *count),
+            Issue::WrongCenter { facelet, .. } => Error::InvalidCenter(*facelet),
+            Issue::InvalidCornerColors { position, .. } => Error::InvalidCornerColors(*position),
+            Issue::InvalidEdgeColors { position, .. } => Error::InvalidEdgeColors(*position),
+            Issue::InvalidCornerOrientation { position } => {
+                Error::InvalidCornerOrientation(*position)
+            }
+            Issue::InvalidEdgeOrientation { position } => Error::InvalidEdgeOrientation(*position),
+            Issue::DuplicateCorner { corner, .. } => Error::DuplicateCorner(*corner),
+            Issue::DuplicateEdge { edge, .. } => Error::DuplicateEdge(*edge),
+            Issue::MissingCorner(corner) => Error::MissingCorner(*corner),
+            Issue::MissingEdge(edge) => Error::MissingEdge(*edge),
+            Issue::TwistedCorner { position, .. } => Error::TwistedCorner(*position),
+            Issue::FlippedEdge { position } => Error::FlippedEdge(*position),
+            Issue::CornerParity { positions: [a, b] } => Error::CornerParity(*a, *b),
+            Issue::EdgeParity { positions: [a, b] } => Error::EdgeParity(*a, *b),
+        }
+    }
+}
+
+/// Result of a cube state validation, lists every issue found.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Converts the report into a result, the error describes the first issue.
+    pub fn into_result(self) -> Result<(), Error> {
+        match self.issues.first() {
+            Some(issue) => Err(issue.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages = self
+            .issues
+            .iter()
+            .map(|issue| Error::from(issue).to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        write!(f, "{messages}")
+    }
+}
+
+/// Finds the positions of each piece, reports duplicates and missing pieces.
+fn check_permutation<T: Copy + PartialEq>(
+    perm: &[T],
+    pieces: &[T],
+    duplicate: impl Fn(T, Vec<T>) -> Issue,
+    missing: impl Fn(T) -> Issue,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for piece in pieces {
+        let positions = pieces
+            .iter()
+            .zip(perm)
+            .filter(|(_, p)| *p == piece)
+            .map(|(position, _)| *position)
+            .collect::<Vec<_>>();
+
+        match positions.len() {
+            0 => issues.push(missing(*piece)),
+            1 => {}
+            _ => issues.push(duplicate(*piece, positions)),
+        }
+    }
+
+    issues
+}
+
+impl CubieCube {
+    /// Checks the state and reports every reason making it unsolvable.
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        for (i, co) in self.co.iter().enumerate() {
+            if *co > 2 {
+                let position = SOLVED_CUBIE_CUBE.cp[i];
+                issues.push(Issue::InvalidCornerOrientation { position });
+            }
+        }
+
+        for (i, eo) in self.eo.iter().enumerate() {
+            if *eo > 1 {
+                let position = SOLVED_CUBIE_CUBE.ep[i];
+                issues.push(Issue::InvalidEdgeOrientation { position });
+            }
+        }
+
+        let has_invalid_orientation = !issues.is_empty();
+
+        issues.extend(check_permutation(
+            &self.cp,
+            &SOLVED_CUBIE_CUBE.cp,
+            |corner, positions| Issue::DuplicateCorner { corner, positions },
+            Issue::MissingCorner,
+        ));
+        issues.extend(check_permutation(
+            &self.ep,
+            &SOLVED_CUBIE_CUBE.ep,
+            |edge, positions| Issue::DuplicateEdge { edge, positions },
+            Issue::MissingEdge,
+        ));
+
+        if has_invalid_orientation {
+            return ValidationReport { issues };
+        }
+
+        let twist = self.co.iter().sum::<u8>() % 3;
+
+        if twist != 0 {
+            let i = self.co.iter().rposition(|co| *co != 0).unwrap();
+            let position = SOLVED_CUBIE_CUBE.cp[i];
+            issues.push(Issue::TwistedCorner { position, twist });
+        }
+
+        if !self.count_edge_twist().is_multiple_of(2) {
+            let i = self.eo.iter().rposition(|eo| *eo != 0).unwrap();
+            let position = SOLVED_CUBIE_CUBE.ep[i];
+            issues.push(Issue::FlippedEdge { position });
+        }
+
+        let has_valid_permutation = issues.iter().all(|issue| {
+            !matches!(
+                issue,
+                Issue::DuplicateCorner { .. }
+                    | Issue::DuplicateEdge { .. }
+                    | Issue::MissingCorner(_)
+                    | Issue::MissingEdge(_)
+            )
+        });
+
+        if has_valid_permutation && self.count_corner_perm() % 2 != self.count_edge_perm() % 2 {
+            let edge = (0..12)
+                .rev()
+                .find(|i| self.ep[*i] != SOLVED_CUBIE_CUBE.ep[*i]);
+
+            // an odd permutation moves at least one piece
+            issues.push(match edge {
+                Some(i) => Issue::EdgeParity {
+                    positions: [SOLVED_CUBIE_CUBE.ep[i], self.ep[i]],
+                },
+                None => {
+                    let i = (0..8)
+                        .rev()
+                        .find(|i| self.cp[*i] != SOLVED_CUBIE_CUBE.cp[*i])
+                        .unwrap();
+
+                    Issue::CornerParity {
+                        positions: [SOLVED_CUBIE_CUBE.cp[i], self.cp[i]],
+                    }
+                }
+            });
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+impl FaceCube {
+    /// Checks the facelets and reports every reason making the cube unsolvable.
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        for (i, color) in SOLVED_COLORS.iter().enumerate() {
+            let count = self.f.iter().filter(|c| *c == color).count();
+
+            if count != 9 {
+                issues.push(Issue::ColorCount {
+                    color: *color,
+                    count,
+                });
+            }
+
+            let facelet = 9 * i + 4;
+
+            if self.f[facelet] != *color {
+                let color = self.f[facelet];
+                issues.push(Issue::WrongCenter { facelet, color });
+            }
+        }
+
+        let mut state = CubieCube::default();
+
+        for (i, facelets) in CORNER_FACELET.iter().enumerate() {
+            let facelets = facelets.map(|f| f as usize);
+            let colors = facelets.map(|f| self.f[f]);
+            let ori = colors.iter().position(|c| *c == Color::U || *c == Color::D);
+            let corner = ori.and_then(|ori| {
+                let colors = [0, 1, 2].map(|k| colors[(ori + k) % 3]);
+                let corner = CORNER_COLOR.iter().position(|c| *c == colors)?;
+                Some((corner, ori))
+            });
+
+            match corner {
+                Some((corner, ori)) => {
+                    state.cp[i] = SOLVED_CUBIE_CUBE.cp[corner];
+                    state.co[i] = ori as u8;
+                }
+                None => issues.push(Issue::InvalidCornerColors {
+                    position: SOLVED_CUBIE_CUBE.cp[i],
+                    facelets,
+                }),
+            }
+        }
+
+        for (i, facelets) in EDGE_FACELET.iter().enumerate() {
+            let facelets = facelets.map(|f| f as usize);
+            let colors = facelets.map(|f| self.f[f]);
+            let flipped = [colors[1], colors[0]];
+            let edge = EDGE_COLOR.iter().enumerate().find_map(|(j, c)| match *c {
+                c if c == colors => Some((j, 0)),
+                c if c == flipped => Some((j, 1)),
+                _ => None,
+            });
+
+            match edge {
+                Some((edge, ori)) => {
+                    state.ep[i] = SOLVED_CUBIE_CUBE.ep[edge];
+                    state.eo[i] = ori;
+                }
+                None => issues.push(Issue::InvalidEdgeColors {
+                    position: SOLVED_CUBIE_CUBE.ep[i],
+                    facelets,
+                }),
+            }
+        }
+
+        if issues.is_empty() {
+            issues = state.validate().issues;
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cube::{cubie::Corner::*, cubie::Edge::*, moves::Move::*};
+
+    #[test]
+    fn test_cubie_issues() {
+        assert!(CubieCube::default().validate().is_valid());
+        assert!(CubieCube::from(&vec![R, U, F2]).validate().is_valid());
+
+        let mut state = CubieCube::default();
+        state.co[0] = 1;
+        assert_eq!(
+            state.validate().issues,
+            vec![Issue::TwistedCorner {
+                position: UBL,
+                twist: 1
+            }]
+        );
+
+        let mut state = CubieCube::default();
+        state.eo[3] = 1;
+        assert_eq!(
+            state.validate().issues,
+            vec![Issue::FlippedEdge { position: FL }]
+        );
+        assert!(matches!(
+            state.validate().into_result(),
+            Err(Error::FlippedEdge(FL))
+        ));
+
+        let mut state = CubieCube::default();
+        state.ep.swap(0, 1);
+        assert_eq!(
+            state.validate().issues,
+            vec![Issue::EdgeParity {
+                positions: [BR, BL]
+            }]
+        );
+
+        let mut state = CubieCube::default();
+        state.cp.swap(2, 5);
+        assert_eq!(
+            state.validate().issues,
+            vec![Issue::CornerParity {
+                positions: [DFR, UFR]
+            }]
+        );
+
+        let mut state = CubieCube::default();
+        state.cp[1] = UBL;
+        assert_eq!(
+            state.validate().issues,
+            vec![
+                Issue::DuplicateCorner {
+                    corner: UBL,
+                    positions: vec![UBL, UBR]
+                },
+                Issue::MissingCorner(UBR)
+            ]
+        );
+        assert!(matches!(
+            state.validate().into_result(),
+            Err(Error::DuplicateCorner(UBL))
+        ));
+
+        let mut state = CubieCube::default();
+        state.co[2] = 3;
+        assert!(matches!(
+            state.validate().into_result(),
+            Err(Error::InvalidCornerOrientation(UFR))
+        ));
+
+        let mut state = CubieCube::default();
+        state.eo[5] = 2;
+        assert!(matches!(
+            state.validate().into_result(),
+            Err(Error::InvalidEdgeOrientation(UR))
+        ));
+    }
+
+    #[test]
+    fn test_facelet_issues() {
+        let faces = "DRBLUURLDRBLRRBFLFFUBFFDRUDURRBDFBBULDUDLUDLBUFFDBFLRL";
+        assert!(FaceCube::try_from(faces).unwrap().validate().is_valid());
+
+        // twisted UFR corner
+        let mut face_cube = FaceCube::default();
+        face_cube.f[8] = Color::F;
+        face_cube.f[9] = Color::U;
+        face_cube.f[20] = Color::R;
+        assert_eq!(
+            face_cube.validate().issues,
+            vec![Issue::TwistedCorner {
+                position: UFR,
+                twist: 1
+            }]
+        );
+        assert!(matches!(
+            CubieCube::try_from(&face_cube),
+            Err(Error::TwistedCorner(UFR))
+        ));
+
+        // a single sticker mistyped on the UF edge
+        let mut face_cube = FaceCube::default();
+        face_cube.f[7] = Color::B;
+        assert_eq!(
+            face_cube.validate().issues,
+            vec![
+                Issue::ColorCount {
+                    color: Color::U,
+                    count: 8
+                },
+                Issue::ColorCount {
+                    color: Color::B,
+                    count: 10
+                },
+                Issue::InvalidEdgeColors {
+                    position: UF,
+                    facelets: [7, 19]
+                }
+            ]
+        );
+
+        // swapped centers
+        let mut face_cube = FaceCube::default();
+        face_cube.f.swap(4, 13);
+        assert_eq!(
+            face_cube.validate().issues[0],
+            Issue::WrongCenter {
+                facelet: 4,
+                color: Color::R
+            }
+        );
+    }
+}
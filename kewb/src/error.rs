@@ -2,6 +2,11 @@ use bincode::error::{DecodeError, EncodeError};
 use std::io;
 use thiserror::Error;
 
+use crate::cube::{
+    cubie::{Corner, Edge},
+    facelet::Color,
+};
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Invalid color value")]
@@ -12,12 +17,50 @@ pub enum Error {
     InvalidCorner,
     #[error("Invalid scramble string")]
     InvalidScramble,
+    #[error("Invalid algorithm token `{token}` at offset {offset}")]
+    InvalidAlgorithm { offset: usize, token: String },
+    #[error("Invalid color scheme, colors must be distinct")]
+    InvalidColorScheme,
     #[error("Invalid facelet string")]
     InvalidFaceletString,
     #[error("Invalid facelet reperesentation")]
     InvalidFaceletValue,
     #[error("Invalid cubie reperesentation")]
     InvalidCubieValue,
+    #[error("Invalid color count, {0} appears {1} times")]
+    InvalidColorCount(Color, usize),
+    #[error("Invalid center color at facelet {0}")]
+    InvalidCenter(usize),
+    #[error("Invalid corner colors at {0:?}")]
+    InvalidCornerColors(Corner),
+    #[error("Invalid edge colors at {0:?}")]
+    InvalidEdgeColors(Edge),
+    #[error("Invalid corner orientation at {0:?}")]
+    InvalidCornerOrientation(Corner),
+    #[error("Invalid edge orientation at {0:?}")]
+    InvalidEdgeOrientation(Edge),
+    #[error("Duplicate corner {0:?}")]
+    DuplicateCorner(Corner),
+    #[error("Duplicate edge {0:?}")]
+    DuplicateEdge(Edge),
+    #[error("Missing corner {0:?}")]
+    MissingCorner(Corner),
+    #[error("Missing edge {0:?}")]
+    MissingEdge(Edge),
+    #[error("Twisted corner at {0:?}")]
+    TwistedCorner(Corner),
+    #[error("Flipped edge at {0:?}")]
+    FlippedEdge(Edge),
+    #[error("Parity error, two pieces are swapped, for instance the corners at {0:?} and {1:?}")]
+    CornerParity(Corner, Corner),
+    #[error("Parity error, two pieces are swapped, for instance the edges at {0:?} and {1:?}")]
+    EdgeParity(Edge, Edge),
+    #[error("Invalid arrow sticker {0}, the U stickers are numbered from 0 to 8")]
+    InvalidArrow(usize),
+    #[error("Outdated table, it must be generated again")]
+    OutdatedTable,
+    #[error("The state cannot be reached with the allowed moves")]
+    UnreachableState,
     #[error("{0}")]
     IOError(#[from] io::Error),
     #[error("{0}")]
@@ -1,10 +1,18 @@
 //! A crate for manipulating and solving the 3x3 Rubik's cube with [Kociemba's two phase algorithm](http://kociemba.org/cube.htm).
 
 pub(crate) mod cube;
+pub(crate) mod optimal;
 pub(crate) mod two_phase;
 
-pub use cube::{cubie::CubieCube, facelet::Color, facelet::FaceCube, moves::Move};
-pub use two_phase::solver::{Solution, Solver};
+pub use cube::{
+    cubie::{Corner, CubieCube, Cycle, Edge},
+    facelet::Color,
+    facelet::FaceCube,
+    facelet::Facelet,
+    moves::{Move, MOVE_CUBES},
+};
+pub use optimal::{restricted::RestrictedSolver, solver::OptimalSolver};
+pub use two_phase::solver::{CancellationToken, Progress, Solution, SolutionEvent, Solver};
 pub use two_phase::utils::DataTable;
 
 /// Module containing 3x3 cube constants.
@@ -17,6 +25,11 @@ pub mod fs {
     pub use crate::two_phase::fs::*;
 }
 
+/// Module containing the coordinates used to build move and pruning tables.
+pub mod coordinate {
+    pub use crate::two_phase::coordinate::*;
+}
+
 /// Module for generating moves table.
 pub mod move_table {
     pub use crate::two_phase::moves::*;
@@ -27,6 +40,16 @@ pub mod pruning_table {
     pub use crate::two_phase::pruning::*;
 }
 
+/// Module for generating the pattern tables of the optimal solver.
+pub mod optimal_table {
+    pub use crate::optimal::pruning::*;
+}
+
+/// Module for generating symmetry reduced pruning tables.
+pub mod symmetry_table {
+    pub use crate::two_phase::symmetry::*;
+}
+
 /// Module for translating permutations and orientations into the two phase algorithm coordinate.
 pub mod index {
     pub use crate::cube::index::*;
@@ -37,9 +60,79 @@ pub mod generators {
     pub use crate::cube::generators::*;
 }
 
+/// Module containing the extended move notation: wide moves, slice moves and cube rotations.
+pub mod notation {
+    pub use crate::cube::notation::*;
+}
+
+/// Module containing the algorithm parser: grouping, repetition, commutators and conjugates.
+pub mod algorithm {
+    pub use crate::cube::algorithm::*;
+}
+
 /// Module containing functions for scrambling the cube.
 pub mod scramble {
     pub use crate::cube::scramble::*;
 }
 
+/// Module containing move count metrics.
+pub mod metrics {
+    pub use crate::cube::metrics::*;
+}
+
+/// Module containing functions for simplifying move sequences.
+pub mod simplify {
+    pub use crate::cube::simplify::*;
+}
+
+/// Module containing move sequence transforms: inverse, mirror and re-orientation.
+pub mod transform {
+    pub use crate::cube::transform::*;
+}
+
+/// Module containing the 48 symmetries of the cube on the cubie level.
+pub mod symmetry {
+    pub use crate::cube::symmetry::*;
+}
+
+/// Module containing the alternative string layouts of a cube.
+pub mod format {
+    pub use crate::cube::format::*;
+}
+
+/// Module containing the groups generated by sets of moves.
+pub mod group {
+    pub use crate::cube::group::*;
+}
+
+/// Module containing the packed cube representation.
+pub mod packed {
+    pub use crate::cube::packed::*;
+}
+
+/// Module containing color schemes for reading and writing facelet strings.
+pub mod scheme {
+    pub use crate::cube::scheme::*;
+}
+
+/// Module containing cubes with oriented centers.
+pub mod supercube {
+    pub use crate::cube::supercube::*;
+}
+
+/// Module containing the SVG rendering of cube states.
+pub mod svg {
+    pub use crate::cube::svg::*;
+}
+
+/// Module containing partially known cube states.
+pub mod mask {
+    pub use crate::cube::mask::*;
+}
+
+/// Module containing the diagnostics of invalid cube states.
+pub mod validation {
+    pub use crate::cube::validation::*;
+}
+
 pub mod error;
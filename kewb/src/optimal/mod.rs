@@ -0,0 +1,3 @@
+pub mod pruning;
+pub mod restricted;
+pub mod solver;
@@ -0,0 +1,480 @@
+use crate::{
+    cube::{
+        cubie::{Corner, Edge},
+        moves::{Move, MOVE_CUBES},
+        packed::PackedCube,
+    },
+    error::Error,
+};
+use bincode::{Decode, Encode};
+
+/// Pieces whose positions and orientations index a pattern table,
+/// built from distinct corners or distinct edges.
+#[derive(Debug, PartialEq, Clone, Encode, Decode)]
+pub struct Pattern {
+    is_corners: bool,
+    /// Tracked pieces, as values of `Corner` or `Edge`.
+    pieces: Vec<u8>,
+}
+
+/// Returns the first piece found twice.
+fn find_duplicate<T: PartialEq + Copy>(pieces: &[T]) -> Option<T> {
+    pieces
+        .iter()
+        .enumerate()
+        .find(|(i, p)| pieces[..*i].contains(p))
+        .map(|(_, p)| *p)
+}
+
+impl Pattern {
+    /// Tracks the given corners, 7 corners give the position and orientation of the last one.
+    pub fn corners(corners: &[Corner]) -> Result<Self, Error> {
+        if let Some(corner) = find_duplicate(corners) {
+            return Err(Error::DuplicateCorner(corner));
+        }
+
+        Ok(Self {
+            is_corners: true,
+            pieces: corners.iter().map(|c| *c as u8).collect(),
+        })
+    }
+
+    /// Tracks the given edges.
+    pub fn edges(edges: &[Edge]) -> Result<Self, Error> {
+        if let Some(edge) = find_duplicate(edges) {
+            return Err(Error::DuplicateEdge(edge));
+        }
+
+        Ok(Self {
+            is_corners: false,
+            pieces: edges.iter().map(|e| *e as u8).collect(),
+        })
+    }
+
+    /// Returns the number of positions and of orientations of the pieces.
+    fn bases(&self) -> (usize, usize) {
+        match self.is_corners {
+            true => (8, 3),
+            false => (12, 2),
+        }
+    }
+
+    /// Returns the number of entries of the pattern table.
+    pub fn size(&self) -> usize {
+        let (positions, orientations) = self.bases();
+        let k = self.pieces.len();
+
+        (positions - k + 1..=positions).product::<usize>() * orientations.pow(k as u32)
+    }
+}
+
+/// Positions and orientations of the tracked pieces of a pattern.
+#[derive(Clone, Copy, Default)]
+struct Slots {
+    positions: [u8; 12],
+    orientations: [u8; 12],
+}
+
+/// Ranks the pieces as a partial permutation followed by their orientations.
+fn encode(slots: &Slots, k: usize, (n, m): (usize, usize)) -> usize {
+    let mut rank = 0;
+    let mut orientation = 0;
+
+    for i in 0..k {
+        let p = slots.positions[i];
+        let smaller = slots.positions[..i].iter().filter(|q| **q < p).count();
+
+        rank = rank * (n - i) + p as usize - smaller;
+        orientation = orientation * m + slots.orientations[i] as usize;
+    }
+
+    rank * m.pow(k as u32) + orientation
+}
+
+fn decode(index: usize, k: usize, (n, m): (usize, usize)) -> Slots {
+    let mut slots = Slots::default();
+    let mut orientation = index % m.pow(k as u32);
+    let mut rank = index / m.pow(k as u32);
+
+    for i in (0..k).rev() {
+        slots.orientations[i] = (orientation % m) as u8;
+        orientation /= m;
+        slots.positions[i] = (rank % (n - i)) as u8;
+        rank /= n - i;
+    }
+
+    // the digits count the free positions, turn them into positions
+    let mut used = [false; 12];
+
+    for i in 0..k {
+        let nth = slots.positions[i] as usize;
+        let p = (0..n).filter(|p| !used[*p]).nth(nth).unwrap();
+        used[p] = true;
+        slots.positions[i] = p as u8;
+    }
+
+    slots
+}
+
+/// Destination of every position and orientation added by each move.
+struct MoveMaps {
+    destination: [[u8; 12]; 18],
+    twist: [[u8; 12]; 18],
+}
+
+impl MoveMaps {
+    fn new(corners: bool) -> Self {
+        let mut maps = Self {
+            destination: [[0; 12]; 18],
+            twist: [[0; 12]; 18],
+        };
+
+        for (m, cube) in MOVE_CUBES.iter().enumerate() {
+            // the piece at position ep[i] moves to position i
+            let pieces: Vec<(u8, u8)> = match corners {
+                true => cube.cp.iter().map(|c| *c as u8).zip(cube.co).collect(),
+                false => cube.ep.iter().map(|e| *e as u8).zip(cube.eo).collect(),
+            };
+
+            for (i, (from, ori)) in pieces.into_iter().enumerate() {
+                maps.destination[m][from as usize] = i as u8;
+                maps.twist[m][i] = ori;
+            }
+        }
+
+        maps
+    }
+
+    fn apply(&self, slots: &Slots, k: usize, m: usize, orientations: u8) -> Slots {
+        let mut next = *slots;
+
+        for i in 0..k {
+            let to = self.destination[m][slots.positions[i] as usize];
+            next.positions[i] = to;
+            next.orientations[i] =
+                (slots.orientations[i] + self.twist[m][to as usize]) % orientations;
+        }
+
+        next
+    }
+}
+
+/// Exact distances of a pattern, stored as 4 bits per entry.
+#[derive(Encode, Decode)]
+pub struct PatternTable {
+    pub pattern: Pattern,
+    distances: Vec<u8>,
+}
+
+impl PatternTable {
+    /// Builds the table with a breadth first search over the pattern, this takes a while for large patterns.
+    /// The search works on the packed distances, so building needs no more memory than the table.
+    pub fn new(pattern: Pattern) -> Self {
+        let size = pattern.size();
+        let k = pattern.pieces.len();
+        let bases = pattern.bases();
+        let maps = MoveMaps::new(pattern.is_corners);
+        let mut distances = vec![u8::MAX; size.div_ceil(2)];
+        let mut depth = 0;
+        let mut filled = 1;
+
+        set_nibble(&mut distances, encode(&solved_slots(&pattern), k, bases), 0);
+
+        // deeper entries keep the empty value, which is still a lower bound
+        while filled != size && depth + 1 < EMPTY {
+            let last_filled = filled;
+            // searching from the empty entries is faster once most of the table is filled
+            let backward = filled > size / 2;
+
+            for i in 0..size {
+                if backward {
+                    if get_nibble(&distances, i) != EMPTY {
+                        continue;
+                    }
+
+                    let slots = decode(i, k, bases);
+
+                    if (0..18).any(|m| {
+                        let j = encode(&maps.apply(&slots, k, m, bases.1 as u8), k, bases);
+                        get_nibble(&distances, j) == depth
+                    }) {
+                        set_nibble(&mut distances, i, depth + 1);
+                        filled += 1;
+                    }
+                } else if get_nibble(&distances, i) == depth {
+                    let slots = decode(i, k, bases);
+
+                    for m in 0..18 {
+                        let j = encode(&maps.apply(&slots, k, m, bases.1 as u8), k, bases);
+
+                        if get_nibble(&distances, j) == EMPTY {
+                            set_nibble(&mut distances, j, depth + 1);
+                            filled += 1;
+                        }
+                    }
+                }
+            }
+
+            if filled == last_filled {
+                break;
+            }
+
+            depth += 1;
+        }
+
+        Self { pattern, distances }
+    }
+
+    /// Builds the table with a breadth first search using only the given moves,
+    /// visiting the entries reachable from the solved state. The search applies the inverse
+    /// moves since the move set may not contain them.
+    pub fn with_moves(pattern: Pattern, moves: &[Move]) -> Self {
+        let size = pattern.size();
+        let k = pattern.pieces.len();
+        let bases = pattern.bases();
+        let maps = MoveMaps::new(pattern.is_corners);
+        let mut distances = vec![u8::MAX; size.div_ceil(2)];
+        let mut frontier = vec![encode(&solved_slots(&pattern), k, bases)];
+        let mut depth = 0;
+
+        set_nibble(&mut distances, frontier[0], 0);
+
+        while !frontier.is_empty() {
+            let mut next = vec![];
+
+            for i in frontier {
+                let slots = decode(i, k, bases);
+
+                for m in moves {
+                    let inverse = m.get_inverse() as usize;
+                    let j = encode(&maps.apply(&slots, k, inverse, bases.1 as u8), k, bases);
+
+                    if get_nibble(&distances, j) == EMPTY {
+                        // larger depths are capped to stay a lower bound
+                        set_nibble(&mut distances, j, (depth + 1).min(EMPTY - 1));
+                        next.push(j);
+                    }
+                }
+            }
+
+            frontier = next;
+            depth += 1;
+        }
+
+        Self { pattern, distances }
+    }
+
+    /// Returns the number of moves needed to solve the pieces of the pattern.
+    pub fn distance(&self, state: &PackedCube) -> u8 {
+        let index = self.index(state);
+
+        get_nibble(&self.distances, index)
+    }
+
+    fn index(&self, state: &PackedCube) -> usize {
+        let bytes = state.as_bytes();
+        let (n, m) = self.pattern.bases();
+        let offset = if self.pattern.is_corners { 0 } else { 8 };
+        let pieces = &self.pattern.pieces;
+        let mut slots = Slots::default();
+
+        for position in 0..n {
+            let value = bytes[offset + position];
+
+            if let Some(i) = pieces
+                .iter()
+                .position(|p| *p as usize == value as usize / m)
+            {
+                slots.positions[i] = position as u8;
+                slots.orientations[i] = value % m as u8;
+            }
+        }
+
+        encode(&slots, pieces.len(), (n, m))
+    }
+
+    /// Returns the memory used by the distances in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.distances.len()
+    }
+}
+
+/// Value of the entries not filled yet, the distances are stored as 4 bits per entry.
+const EMPTY: u8 = 15;
+
+fn get_nibble(distances: &[u8], index: usize) -> u8 {
+    distances[index / 2] >> (4 * (index % 2)) & 15
+}
+
+fn set_nibble(distances: &mut [u8], index: usize, value: u8) {
+    let shift = 4 * (index % 2);
+    distances[index / 2] = distances[index / 2] & !(15 << shift) | value << shift;
+}
+
+fn solved_slots(pattern: &Pattern) -> Slots {
+    let mut slots = Slots::default();
+
+    for (i, p) in pattern.pieces.iter().enumerate() {
+        slots.positions[i] = *p;
+    }
+
+    slots
+}
+
+/// Preset used without memory budget: the corners and two sets of 6 edges.
+const DEFAULT_PRESET: usize = 1;
+
+/// Pattern sets from the strongest to the smallest, picked according to the memory budget.
+fn presets() -> Vec<Vec<Pattern>> {
+    let corners = || Pattern {
+        is_corners: true,
+        pieces: (0..7).collect(),
+    };
+    let edges = |range: std::ops::Range<u8>| Pattern {
+        is_corners: false,
+        pieces: range.collect(),
+    };
+
+    vec![
+        vec![corners(), edges(0..7), edges(5..12)],
+        vec![corners(), edges(0..6), edges(6..12)],
+        vec![corners(), edges(0..5), edges(6..11)],
+        vec![edges(0..5), edges(6..11)],
+        vec![edges(0..4), edges(4..8), edges(8..12)],
+        vec![],
+    ]
+}
+
+/// Pattern tables giving a lower bound of the number of moves needed to solve a state.
+#[derive(Encode, Decode)]
+pub struct OptimalTable {
+    pub tables: Vec<PatternTable>,
+}
+
+impl Default for OptimalTable {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl OptimalTable {
+    /// Builds the strongest tables fitting in the memory budget, in bytes.
+    /// The tables are built in place, the budget also bounds the memory used while building them.
+    /// Without budget, the corners and two sets of 6 edges are used, about 87MB.
+    pub fn new(memory_budget: Option<usize>) -> Self {
+        let mut presets = presets();
+        let patterns = match memory_budget {
+            Some(budget) => presets
+                .into_iter()
+                .find(|patterns| {
+                    patterns.iter().map(|p| p.size().div_ceil(2)).sum::<usize>() <= budget
+                })
+                .unwrap_or_default(),
+            None => presets.swap_remove(DEFAULT_PRESET),
+        };
+
+        Self::from_patterns(patterns)
+    }
+
+    /// Builds the tables of the given patterns.
+    pub fn from_patterns(patterns: Vec<Pattern>) -> Self {
+        Self {
+            tables: patterns.into_iter().map(PatternTable::new).collect(),
+        }
+    }
+
+    /// Returns a lower bound of the number of moves needed to solve the state.
+    pub fn lower_bound(&self, state: &PackedCube) -> u8 {
+        self.tables
+            .iter()
+            .map(|t| t.distance(state))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the memory used by the tables in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.tables.iter().map(|t| t.memory_usage()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        generators::generate_random_state, two_phase::utils::ALL_MOVES, CubieCube, Edge::*, Move::*,
+    };
+
+    #[test]
+    fn test_encoding() {
+        let patterns = [
+            Pattern::edges(&[BL, BR, FR, FL]).unwrap(),
+            Pattern::edges(&[FR, UR, UL, DR, DL]).unwrap(),
+        ];
+
+        assert_eq!(presets()[0][0].size(), 88_179_840);
+
+        for pattern in patterns {
+            let k = pattern.pieces.len();
+            let bases = pattern.bases();
+
+            for i in (0..pattern.size()).step_by(97) {
+                assert_eq!(encode(&decode(i, k, bases), k, bases), i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pattern_table() {
+        let pattern = Pattern::edges(&[UB, UR, UF, UL]).unwrap();
+        let table = PatternTable::new(pattern.clone());
+        let solved = PackedCube::default();
+
+        assert_eq!(table.distance(&solved), 0);
+        assert_eq!(table.distance(&solved.apply_move(U)), 1);
+        assert_eq!(table.distance(&solved.apply_move(D)), 0);
+        assert_eq!(table.distance(&solved.apply_moves(&[U, R2])), 2);
+
+        let all_moves = PatternTable::with_moves(pattern, &ALL_MOVES);
+        assert_eq!(all_moves.distances, table.distances);
+
+        // the table matches the move maps used to build it
+        let state = generate_random_state();
+        let maps = MoveMaps::new(false);
+        let pieces = [4, 5, 6, 7];
+        let bases = (12, 2);
+        let packed = PackedCube::from(&state);
+        let slots = decode(table.index(&packed), 4, bases);
+
+        for m in [R, F3, B2, L] {
+            let moved = maps.apply(&slots, 4, m as usize, 2);
+            let expected = PackedCube::from(&CubieCube::from(packed).apply_move(m));
+
+            assert_eq!(encode(&moved, pieces.len(), bases), table.index(&expected));
+        }
+    }
+
+    #[test]
+    fn test_pattern_validation() {
+        use crate::{error::Error, Corner};
+
+        assert!(matches!(
+            Pattern::edges(&[UB, UR, UB]),
+            Err(Error::DuplicateEdge(UB))
+        ));
+        assert!(matches!(
+            Pattern::corners(&[Corner::UFR, Corner::UFR]),
+            Err(Error::DuplicateCorner(Corner::UFR))
+        ));
+        assert_eq!(Pattern::corners(&[Corner::DBL]).unwrap().size(), 24);
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let table = OptimalTable::new(Some(1 << 20));
+
+        assert_eq!(table.tables.len(), 3);
+        assert!(table.memory_usage() <= 1 << 20);
+        assert!(OptimalTable::new(Some(0)).tables.is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+use super::{
+    pruning::{OptimalTable, Pattern, PatternTable},
+    solver::Search,
+};
+use crate::{
+    cube::{
+        cubie::{Corner, CubieCube, Edge},
+        group::MoveGroup,
+        moves::{Move, MOVE_CUBES},
+        packed::PackedCube,
+    },
+    error::Error,
+};
+
+/// Maximum number of pieces tracked by a pattern table of the restricted solver.
+const MAX_PATTERN_PIECES: usize = 6;
+
+/// Returns the pieces moved by at least one of the moves, as corners and edges.
+fn moved_pieces(moves: &[Move]) -> (Vec<Corner>, Vec<Edge>) {
+    let cubes: Vec<&CubieCube> = moves.iter().map(|m| &MOVE_CUBES[*m as usize]).collect();
+    let corners = (0..8)
+        .filter(|i| {
+            cubes
+                .iter()
+                .any(|c| c.cp[*i] as usize != *i || c.co[*i] != 0)
+        })
+        .map(|i| Corner::try_from(i as u8).unwrap())
+        .collect();
+    let edges = (0..12)
+        .filter(|i| {
+            cubes
+                .iter()
+                .any(|c| c.ep[*i] as usize != *i || c.eo[*i] != 0)
+        })
+        .map(|i| Edge::try_from(i as u8).unwrap())
+        .collect();
+
+    (corners, edges)
+}
+
+/// Patterns covering the moved pieces: up to 6 corners and the edges split in sets of at most 6.
+fn patterns(moves: &[Move]) -> Vec<Pattern> {
+    let (corners, edges) = moved_pieces(moves);
+    let mut patterns = vec![];
+
+    if !corners.is_empty() {
+        let count = corners.len().min(MAX_PATTERN_PIECES);
+        patterns.push(Pattern::corners(&corners[..count]).unwrap());
+    }
+
+    if !edges.is_empty() {
+        let sets = edges.len().div_ceil(MAX_PATTERN_PIECES);
+        let chunk = edges.len().div_ceil(sets);
+        patterns.extend(edges.chunks(chunk).map(|c| Pattern::edges(c).unwrap()));
+    }
+
+    patterns
+}
+
+/// Optimal solver using only a subset of the moves, such as `<R, U>` for 2-gen solutions.
+pub struct RestrictedSolver {
+    moves: Vec<Move>,
+    group: MoveGroup,
+    table: OptimalTable,
+    max_length: u8,
+}
+
+impl RestrictedSolver {
+    /// Builds the group generated by the moves and the pattern tables of the pieces they move.
+    pub fn new(moves: &[Move]) -> Self {
+        let mut moves = moves.to_vec();
+        moves.sort_by_key(|m| *m as usize);
+        moves.dedup();
+
+        let tables = patterns(&moves)
+            .into_iter()
+            .map(|pattern| PatternTable::with_moves(pattern, &moves))
+            .collect();
+
+        Self {
+            group: MoveGroup::new(&moves),
+            table: OptimalTable { tables },
+            moves,
+            max_length: u8::MAX,
+        }
+    }
+
+    /// Returns the allowed moves.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Returns the group generated by the allowed moves.
+    pub fn group(&self) -> &MoveGroup {
+        &self.group
+    }
+
+    /// Sets the maximum number of moves of the solutions, `u8::MAX` by default.
+    pub fn set_max_length(&mut self, max_length: u8) {
+        self.max_length = max_length;
+    }
+
+    /// Returns a solution using only the allowed moves with the minimum number of moves,
+    /// `None` if every solution is longer than the maximum length.
+    /// Fails with `Error::UnreachableState` if the state is not in the group generated by the moves.
+    pub fn solve(&mut self, state: CubieCube) -> Result<Option<Vec<Move>>, Error> {
+        if !self.group.contains(&state) {
+            return Err(Error::UnreachableState);
+        }
+
+        let is_available = |prev, current| is_available(&self.moves, prev, current);
+
+        Ok(Search::new(&self.table, &self.moves, is_available)
+            .run(PackedCube::from(&state), self.max_length))
+    }
+}
+
+/// Checks if the move can follow the previous one, successive turns of a face are only
+/// skipped when their combination is allowed, opposite faces are turned in a fixed order.
+fn is_available(moves: &[Move], prev: Move, current: Move) -> bool {
+    let (prev_face, face) = (prev as usize / 3, current as usize / 3);
+
+    if prev_face == face {
+        let power = prev.get_power() + current.get_power();
+
+        return Move::from_face(current.get_face(), power).is_some_and(|m| !moves.contains(&m));
+    }
+
+    !(prev_face / 2 == face / 2 && face < prev_face)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Move::*;
+
+    #[test]
+    fn test_restricted_solve() {
+        let mut solver = RestrictedSolver::new(&[R, R2, R3, U, U2, U3]);
+        let scramble = vec![R, U, R3, U, R, U2, R3];
+        let state = CubieCube::from(&scramble);
+        let solution = solver.solve(state).unwrap().unwrap();
+
+        // the Sune is optimal in the half turn metric
+        assert_eq!(solution.len(), scramble.len());
+        assert!(solution.iter().all(|m| solver.moves().contains(m)));
+        assert!(state.apply_moves(&solution).is_solved());
+        assert!(matches!(
+            solver.solve(CubieCube::from(&vec![R, F])),
+            Err(Error::UnreachableState)
+        ));
+
+        // only quarter turns clockwise, U2 is written as U U
+        let mut solver = RestrictedSolver::new(&[R, U]);
+        let solution = solver.solve(CubieCube::from(&vec![U2, R3])).unwrap();
+
+        assert_eq!(solution, Some(vec![R, U, U]));
+
+        // reachable, but every solution is longer than the maximum length
+        solver.set_max_length(2);
+        assert!(matches!(
+            solver.solve(CubieCube::from(&vec![U2, R3])),
+            Ok(None)
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use super::pruning::OptimalTable;
+use crate::cube::{cubie::CubieCube, moves::Move, packed::PackedCube};
+
+/// Every position of the cube can be solved in 20 moves or fewer.
+pub const GODS_NUMBER: u8 = 20;
+
+const MOVES: [Move; 18] = {
+    use Move::*;
+    [
+        U, U2, U3, D, D2, D3, R, R2, R3, L, L2, L3, F, F2, F3, B, B2, B3,
+    ]
+};
+
+/// Checks if the move can follow the previous one in a canonical sequence:
+/// a face is never turned twice in a row and opposite faces are turned in a fixed order.
+fn is_canonical(prev: Move, current: Move) -> bool {
+    let (prev_face, face) = (prev as usize / 3, current as usize / 3);
+
+    !(prev_face / 2 == face / 2 && face <= prev_face)
+}
+
+/// IDA* search shared by the optimal solvers, deepening the bound from the lower bound
+/// given by the pattern tables up to a maximum length.
+pub(super) struct Search<'a, F> {
+    table: &'a OptimalTable,
+    moves: &'a [Move],
+    /// Checks if a move can follow the previous one.
+    is_available: F,
+    path: Vec<Move>,
+}
+
+impl<'a, F: Fn(Move, Move) -> bool> Search<'a, F> {
+    pub(super) fn new(table: &'a OptimalTable, moves: &'a [Move], is_available: F) -> Self {
+        Self {
+            table,
+            moves,
+            is_available,
+            path: vec![],
+        }
+    }
+
+    /// Returns a shortest sequence of the moves solving the state,
+    /// `None` if every solution is longer than `max_length`.
+    pub(super) fn run(mut self, state: PackedCube, max_length: u8) -> Option<Vec<Move>> {
+        for bound in self.table.lower_bound(&state)..=max_length {
+            if self.search(state, bound) {
+                return Some(self.path);
+            }
+        }
+
+        None
+    }
+
+    fn search(&mut self, state: PackedCube, togo: u8) -> bool {
+        if togo == 0 {
+            return state.is_solved();
+        }
+
+        if self.table.lower_bound(&state) > togo {
+            return false;
+        }
+
+        for &m in self.moves {
+            if let Some(prev) = self.path.last() {
+                if !(self.is_available)(*prev, m) {
+                    continue;
+                }
+            }
+
+            self.path.push(m);
+
+            if self.search(state.apply_move(m), togo - 1) {
+                return true;
+            }
+
+            self.path.pop();
+        }
+
+        false
+    }
+}
+
+/// Optimal solver, an IDA* search over the 18 moves using pattern tables as heuristic.
+pub struct OptimalSolver<'a> {
+    table: &'a OptimalTable,
+}
+
+impl<'a> OptimalSolver<'a> {
+    pub fn new(table: &'a OptimalTable) -> Self {
+        Self { table }
+    }
+
+    /// Returns a solution with the minimum number of moves in the half turn metric,
+    /// `None` if the state is not solvable.
+    pub fn solve(&mut self, state: CubieCube) -> Option<Vec<Move>> {
+        if !state.is_solvable() {
+            return None;
+        }
+
+        Search::new(self.table, &MOVES, is_canonical).run(PackedCube::from(&state), GODS_NUMBER)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{generators::generate_random_state, Move::*};
+
+    #[test]
+    fn test_canonical() {
+        assert!(is_canonical(U, D));
+        assert!(!is_canonical(D, U2));
+        assert!(!is_canonical(R, R3));
+        assert!(is_canonical(L, U));
+    }
+
+    #[test]
+    fn test_optimal_solve() {
+        let table = OptimalTable::new(Some(1 << 20));
+        let mut solver = OptimalSolver::new(&table);
+        let scrambles = [vec![R, U, R3, U3], vec![F, R2, D3, B, L]];
+
+        for scramble in scrambles {
+            let state = CubieCube::from(&scramble);
+            let solution = solver.solve(state).unwrap();
+
+            assert_eq!(solution.len(), scramble.len());
+            assert!(state.apply_moves(&solution).is_solved());
+        }
+
+        let mut invalid = generate_random_state();
+        invalid.co[0] = (invalid.co[0] + 1) % 3;
+        assert!(solver.solve(invalid).is_none());
+        assert!(solver.solve(CubieCube::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    #[ignore = "builds the default tables"]
+    fn test_default_table() {
+        // the Anaconda pattern is optimal in 14 moves
+        let table = OptimalTable::default();
+        let mut solver = OptimalSolver::new(&table);
+        let anaconda = CubieCube::from(&vec![L, U, B3, U3, R, L3, B, R3, F, B3, D, R, D3, F3]);
+        let solution = solver.solve(anaconda).unwrap();
+
+        assert_eq!(solution.len(), 14);
+        assert!(anaconda.apply_moves(&solution).is_solved());
+    }
+
+    #[test]
+    #[ignore = "builds the default tables and searches for hours"]
+    fn test_superflip() {
+        let table = OptimalTable::default();
+        let mut solver = OptimalSolver::new(&table);
+        let superflip = CubieCube {
+            eo: [1; 12],
+            ..CubieCube::default()
+        };
+        let solution = solver.solve(superflip).unwrap();
+
+        assert_eq!(solution.len(), 20);
+        assert!(superflip.apply_moves(&solution).is_solved());
+    }
+}
@@ -0,0 +1,250 @@
+use super::utils::{ALL_MOVES, PHASE2_MOVES};
+use crate::cube::{constants::*, cubie::CubieCube, index::*, moves::Move};
+
+/// Value describing part of a state, used to build move and pruning tables.
+/// Coordinate 0 must be the solved state.
+pub trait Coordinate {
+    /// Number of distinct values of the coordinate.
+    const COUNT: usize;
+
+    /// Moves the tables are built for, in table column order.
+    fn moves() -> &'static [Move];
+
+    /// Returns the coordinate of the given state.
+    fn encode(state: &CubieCube) -> usize;
+
+    /// Returns any state having the given coordinate.
+    fn decode(index: usize) -> CubieCube;
+}
+
+/// Corner orientation, phase 1.
+pub struct CornerOrientation;
+
+/// Edge orientation, phase 1.
+pub struct EdgeOrientation;
+
+/// Positions of the E-slice edges, phase 1.
+pub struct ESliceCombination;
+
+/// Corner permutation, phase 2.
+pub struct CornerPermutation;
+
+/// Permutation of the U and D layer edges, phase 2.
+pub struct UdEdgePermutation;
+
+/// Permutation of the E-slice edges, phase 2.
+pub struct ESliceEdgePermutation;
+
+impl Coordinate for CornerOrientation {
+    const COUNT: usize = CO_COUNT as usize;
+
+    fn moves() -> &'static [Move] {
+        &ALL_MOVES
+    }
+
+    fn encode(state: &CubieCube) -> usize {
+        co_to_index(&state.co).into()
+    }
+
+    fn decode(index: usize) -> CubieCube {
+        CubieCube {
+            co: index_to_co(index as u16),
+            ..Default::default()
+        }
+    }
+}
+
+impl Coordinate for EdgeOrientation {
+    const COUNT: usize = EO_COUNT as usize;
+
+    fn moves() -> &'static [Move] {
+        &ALL_MOVES
+    }
+
+    fn encode(state: &CubieCube) -> usize {
+        eo_to_index(&state.eo).into()
+    }
+
+    fn decode(index: usize) -> CubieCube {
+        CubieCube {
+            eo: index_to_eo(index as u16),
+            ..Default::default()
+        }
+    }
+}
+
+impl Coordinate for ESliceCombination {
+    const COUNT: usize = E_COMBO_COUNT as usize;
+
+    fn moves() -> &'static [Move] {
+        &ALL_MOVES
+    }
+
+    fn encode(state: &CubieCube) -> usize {
+        e_combo_to_index(&state.ep).into()
+    }
+
+    fn decode(index: usize) -> CubieCube {
+        CubieCube {
+            ep: index_to_e_combo(index as u16),
+            ..Default::default()
+        }
+    }
+}
+
+impl Coordinate for CornerPermutation {
+    const COUNT: usize = CP_COUNT as usize;
+
+    fn moves() -> &'static [Move] {
+        &PHASE2_MOVES
+    }
+
+    fn encode(state: &CubieCube) -> usize {
+        cp_to_index(&state.cp).into()
+    }
+
+    fn decode(index: usize) -> CubieCube {
+        CubieCube {
+            cp: index_to_cp(index as u16),
+            ..Default::default()
+        }
+    }
+}
+
+impl Coordinate for UdEdgePermutation {
+    const COUNT: usize = UD_EP_COUNT as usize;
+
+    fn moves() -> &'static [Move] {
+        &PHASE2_MOVES
+    }
+
+    fn encode(state: &CubieCube) -> usize {
+        ud_ep_to_index(&state.ep).into()
+    }
+
+    fn decode(index: usize) -> CubieCube {
+        CubieCube {
+            ep: index_to_ud_ep(index as u16),
+            ..Default::default()
+        }
+    }
+}
+
+impl Coordinate for ESliceEdgePermutation {
+    const COUNT: usize = E_EP_COUNT as usize;
+
+    fn moves() -> &'static [Move] {
+        &PHASE2_MOVES
+    }
+
+    fn encode(state: &CubieCube) -> usize {
+        e_ep_to_index(&state.ep).into()
+    }
+
+    fn decode(index: usize) -> CubieCube {
+        CubieCube {
+            ep: index_to_e_ep(index as u16),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        cube::cubie::{Corner, Edge},
+        move_table::get_move_table,
+        pruning_table::get_coord_prune_table,
+        Move::*,
+    };
+
+    const MOVES: [Move; 18] = [
+        U, U2, U3, D, D2, D3, R, R2, R3, L, L2, L3, F, F2, F3, B, B2, B3,
+    ];
+
+    /// Position and orientation of the DFR corner.
+    struct PairCorner;
+
+    /// Position and orientation of the FR edge.
+    struct PairEdge;
+
+    impl Coordinate for PairCorner {
+        const COUNT: usize = 24;
+
+        fn moves() -> &'static [Move] {
+            &MOVES
+        }
+
+        fn encode(state: &CubieCube) -> usize {
+            let (position, ori) = state.locate_corner(Corner::DFR).unwrap();
+            (position as usize ^ Corner::DFR as usize) * 3 + ori as usize
+        }
+
+        fn decode(index: usize) -> CubieCube {
+            let mut state = CubieCube::default();
+            let position = (index / 3) ^ Corner::DFR as usize;
+
+            state.cp.swap(position, Corner::DFR as usize);
+            state.co[position] = (index % 3) as u8;
+            state
+        }
+    }
+
+    impl Coordinate for PairEdge {
+        const COUNT: usize = 24;
+
+        fn moves() -> &'static [Move] {
+            &MOVES
+        }
+
+        fn encode(state: &CubieCube) -> usize {
+            let (position, ori) = state.locate_edge(Edge::FR).unwrap();
+            (position as usize ^ Edge::FR as usize) * 2 + ori as usize
+        }
+
+        fn decode(index: usize) -> CubieCube {
+            let mut state = CubieCube::default();
+            let position = (index / 2) ^ Edge::FR as usize;
+
+            state.ep.swap(position, Edge::FR as usize);
+            state.eo[position] = (index % 2) as u8;
+            state
+        }
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let state = CubieCube::from(&vec![R, U, F2, D3, L]);
+
+        for i in 0..PairCorner::COUNT {
+            assert_eq!(PairCorner::encode(&PairCorner::decode(i)), i);
+        }
+
+        for i in 0..PairEdge::COUNT {
+            assert_eq!(PairEdge::encode(&PairEdge::decode(i)), i);
+        }
+
+        let table = get_move_table::<CornerOrientation, u16>();
+        let co = CornerOrientation::encode(&state);
+        assert_eq!(
+            table[co][6] as usize,
+            CornerOrientation::encode(&state.apply_move(R))
+        );
+    }
+
+    #[test]
+    fn test_custom_prune_table() {
+        let table = get_coord_prune_table::<PairCorner, PairEdge>();
+        let state = CubieCube::from(&vec![R, U, R3]);
+
+        assert_eq!(table[0][0], 0);
+        assert!(table.iter().flatten().all(|d| *d != u8::MAX));
+        assert!(table[PairCorner::encode(&state)][PairEdge::encode(&state)] <= 3);
+
+        // only equal values can be reached when pairing a coordinate with itself
+        let table = get_coord_prune_table::<PairCorner, PairCorner>();
+        assert_ne!(table[1][1], u8::MAX);
+        assert_eq!(table[1][0], u8::MAX);
+    }
+}
@@ -9,12 +9,42 @@ use std::{fs, path::Path};
 
 const CONFIG: Configuration = config::standard();
 
+/// Header written before the encoded table, the version changes with the table format.
+const MAGIC: &[u8; 4] = b"KEWB";
+const TABLE_VERSION: u32 = 2;
+
+fn header() -> Vec<u8> {
+    let mut header = MAGIC.to_vec();
+    header.extend(TABLE_VERSION.to_le_bytes());
+    header
+}
+
+/// Checks if the bytes start with the header of the current table format.
+pub fn is_current_table(bytes: &[u8]) -> bool {
+    bytes.starts_with(&header())
+}
+
 pub fn write_table<P>(path: P) -> Result<(), Error>
 where
     P: AsRef<Path>,
 {
-    let table = DataTable::default();
-    let encoded = encode_to_vec(table, CONFIG)?;
+    encode_table(DataTable::default(), path)
+}
+
+/// Writes the table including the symmetry reduced pruning tables, see `DataTable::with_symmetry`.
+pub fn write_symmetry_table<P>(path: P) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    encode_table(DataTable::with_symmetry(), path)
+}
+
+fn encode_table<P>(table: DataTable, path: P) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+{
+    let mut encoded = header();
+    encoded.extend(encode_to_vec(table, CONFIG)?);
 
     fs::write(path, encoded)?;
 
@@ -31,13 +61,50 @@ where
     Ok(table)
 }
 
+/// Decodes a table written by `write_table`, tables written by another version are rejected.
 pub fn decode_table(bytes: &[u8]) -> Result<DataTable, Error> {
+    if !is_current_table(bytes) {
+        return Err(Error::OutdatedTable);
+    }
+
+    let bytes = &bytes[header().len()..];
     let (decoded, written) = decode_from_slice(bytes, CONFIG)?;
     let additional = bytes.len() - written;
 
     if additional != 0 {
-        return Err(DecodeError::UnexpectedEnd { additional })?;
+        return Err(DecodeError::UnexpectedEnd { additional }.into());
     }
 
     Ok(decoded)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_table_version() {
+        let path = std::env::temp_dir().join("kewb_test_table.bin");
+
+        write_table(&path).unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        assert!(is_current_table(&bytes));
+        assert!(read_table(&path).is_ok());
+
+        // a table written without header, or by an older version, is outdated
+        let outdated = encode_to_vec(DataTable::default(), CONFIG).unwrap();
+        assert!(!is_current_table(&outdated));
+        assert!(matches!(decode_table(&outdated), Err(Error::OutdatedTable)));
+
+        let mut old_version = MAGIC.to_vec();
+        old_version.extend(1u32.to_le_bytes());
+        old_version.extend(&bytes[header().len()..]);
+        assert!(matches!(
+            decode_table(&old_version),
+            Err(Error::OutdatedTable)
+        ));
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
+pub mod coordinate;
 pub mod fs;
 pub mod moves;
 pub mod pruning;
 pub mod solver;
+pub mod symmetry;
 pub mod utils;
@@ -1,5 +1,4 @@
-use super::utils::*;
-use crate::cube::{constants::*, cubie::CubieCube, index::*};
+use super::{coordinate::*, utils::*};
 use bincode::{Decode, Encode};
 
 /// Collection of moves table for navigating on the coordinate level.
@@ -26,109 +25,42 @@ impl Default for MoveTable {
     }
 }
 
-pub fn get_co_table() -> Table<u16> {
-    let mut co_table = vec![vec![0; 18]; CO_COUNT as usize];
+/// Builds the move table of any coordinate, one row per coordinate and one column per move.
+pub fn get_move_table<C: Coordinate, T: TableIndex>() -> Table<T> {
+    let moves = C::moves();
+    let mut table = vec![vec![T::from_index(0); moves.len()]; C::COUNT];
 
-    for i in 0..CO_COUNT {
-        let state = CubieCube {
-            co: index_to_co(i),
-            ..Default::default()
-        };
+    for (i, row) in table.iter_mut().enumerate() {
+        let state = C::decode(i);
 
-        for (j, m) in ALL_MOVES.iter().enumerate() {
-            let new_state = state.apply_move(*m);
-            co_table[i as usize][j] = co_to_index(&new_state.co);
+        for (j, m) in moves.iter().enumerate() {
+            row[j] = T::from_index(C::encode(&state.apply_move(*m)));
         }
     }
 
-    co_table
+    table
 }
 
-pub fn get_eo_table() -> Table<u16> {
-    let mut eo_table = vec![vec![0; 18]; EO_COUNT as usize];
-
-    for i in 0..EO_COUNT {
-        let state = CubieCube {
-            eo: index_to_eo(i),
-            ..Default::default()
-        };
-
-        for (j, m) in ALL_MOVES.iter().enumerate() {
-            let new_state = state.apply_move(*m);
-            eo_table[i as usize][j] = eo_to_index(&new_state.eo);
-        }
-    }
+pub fn get_co_table() -> Table<u16> {
+    get_move_table::<CornerOrientation, u16>()
+}
 
-    eo_table
+pub fn get_eo_table() -> Table<u16> {
+    get_move_table::<EdgeOrientation, u16>()
 }
 
 pub fn get_e_combo_table() -> Table<u16> {
-    let mut e_combo_table = vec![vec![0; 18]; E_COMBO_COUNT as usize];
-    for i in 0..E_COMBO_COUNT {
-        let state = CubieCube {
-            ep: index_to_e_combo(i),
-            ..Default::default()
-        };
-
-        for (j, m) in ALL_MOVES.iter().enumerate() {
-            let new_state = state.apply_move(*m);
-            e_combo_table[i as usize][j] = e_combo_to_index(&new_state.ep);
-        }
-    }
-
-    e_combo_table
+    get_move_table::<ESliceCombination, u16>()
 }
 
 pub fn get_cp_table() -> Table<u16> {
-    let mut cp_table = vec![vec![0; 10]; CP_COUNT as usize];
-
-    for i in 0..CP_COUNT {
-        let state = CubieCube {
-            cp: index_to_cp(i),
-            ..Default::default()
-        };
-
-        for (j, m) in PHASE2_MOVES.iter().enumerate() {
-            let new_state = state.apply_move(*m);
-            cp_table[i as usize][j] = cp_to_index(&new_state.cp);
-        }
-    }
-
-    cp_table
+    get_move_table::<CornerPermutation, u16>()
 }
 
 pub fn get_ud_ep_table() -> Table<u16> {
-    let mut ep_table = vec![vec![0; 10]; UD_EP_COUNT as usize];
-
-    for i in 0..UD_EP_COUNT {
-        let state = CubieCube {
-            ep: index_to_ud_ep(i),
-            ..Default::default()
-        };
-
-        for (j, m) in PHASE2_MOVES.iter().enumerate() {
-            let new_state = state.apply_move(*m);
-            ep_table[i as usize][j] = ud_ep_to_index(&new_state.ep);
-        }
-    }
-
-    ep_table
+    get_move_table::<UdEdgePermutation, u16>()
 }
 
 pub fn get_e_ep_table() -> Table<u16> {
-    let mut e_ep_table = vec![vec![0; 10]; E_EP_COUNT as usize];
-
-    for i in 0..E_EP_COUNT {
-        let state = CubieCube {
-            ep: index_to_e_ep(i),
-            ..Default::default()
-        };
-
-        for (j, m) in PHASE2_MOVES.iter().enumerate() {
-            let new_state = state.apply_move(*m);
-            e_ep_table[i as usize][j] = e_ep_to_index(&new_state.ep);
-        }
-    }
-
-    e_ep_table
+    get_move_table::<ESliceEdgePermutation, u16>()
 }
@@ -1,6 +1,6 @@
 use crate::cube::moves::Move;
 
-use super::{moves::*, utils::*};
+use super::{coordinate::Coordinate, moves::*, utils::*};
 use bincode::{Decode, Encode};
 
 /// Collection of pruning table for filtering unsolvable state at a given depth.
@@ -23,7 +23,27 @@ impl Default for PruningTable {
     }
 }
 
-pub fn get_prune_table(table1: Table<u16>, table2: Table<u16>, moves: &[Move]) -> Table<u8> {
+/// Builds the pruning table of two coordinates using the same moves,
+/// pairs which can't be reached stay at `u8::MAX`.
+pub fn get_coord_prune_table<C1: Coordinate, C2: Coordinate>() -> Table<u8> {
+    assert_eq!(
+        C1::moves(),
+        C2::moves(),
+        "coordinates must share their moves"
+    );
+
+    get_prune_table(
+        get_move_table::<C1, u32>(),
+        get_move_table::<C2, u32>(),
+        C1::moves(),
+    )
+}
+
+pub fn get_prune_table<T: TableIndex>(
+    table1: Table<T>,
+    table2: Table<T>,
+    moves: &[Move],
+) -> Table<u8> {
     let len1 = table1.len();
     let len2 = table2.len();
     let fill_size = len1 * len2;
@@ -34,12 +54,14 @@ pub fn get_prune_table(table1: Table<u16>, table2: Table<u16>, moves: &[Move]) -
     pruning_table[0][0] = 0;
 
     while filled != fill_size {
+        let last_filled = filled;
+
         for (i, ti) in table1.iter().enumerate() {
             for (j, tj) in table2.iter().enumerate() {
                 if pruning_table[i][j] == distance {
                     for m in 0..moves.len() {
-                        let k = ti[m] as usize;
-                        let l = tj[m] as usize;
+                        let k = ti[m].to_index();
+                        let l = tj[m].to_index();
 
                         if pruning_table[k][l] == u8::MAX {
                             pruning_table[k][l] = distance + 1;
@@ -50,6 +72,11 @@ pub fn get_prune_table(table1: Table<u16>, table2: Table<u16>, moves: &[Move]) -
             }
         }
 
+        // the remaining pairs can't be reached
+        if filled == last_filled {
+            break;
+        }
+
         distance += 1;
     }
 
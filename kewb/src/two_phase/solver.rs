@@ -1,22 +1,31 @@
 use std::{
     fmt,
+    ops::ControlFlow,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+        Arc,
+    },
+    thread,
     time::{Duration, Instant},
 };
 
 use crate::cube::{
     cubie::CubieCube,
     index::*,
-    moves::{is_move_available, Move},
+    metrics::{move_count, Metric},
+    moves::{is_move_available, Move, MOVE_CUBES},
+    simplify::simplify,
+    supercube::{get_center_fix, SuperCube},
+    symmetry::get_inverse_symmetry,
+    transform::invert,
 };
 
-use super::pruning::PruningTable;
-use super::utils::{ALL_MOVES, PHASE2_MOVES};
-use super::{moves::MoveTable, utils::DataTable};
+use super::utils::{DataTable, ALL_MOVES, PHASE2_MOVES};
 
 trait Phase {
     fn is_solved(&self) -> bool;
-    fn next(&self, table: &MoveTable, move_index: usize) -> Self;
-    fn prune(&self, table: &PruningTable, depth: u8) -> bool;
+    fn next(&self, table: &DataTable, move_index: usize) -> Self;
+    fn prune(&self, table: &DataTable, depth: u8) -> bool;
 }
 
 #[derive(Debug)]
@@ -24,6 +33,26 @@ struct Phase1State {
     co_index: usize,
     eo_index: usize,
     e_combo_index: usize,
+    /// Exact distance to phase 2, only tracked with the symmetry table.
+    distance: u8,
+}
+
+impl Phase1State {
+    fn new(value: CubieCube, table: &DataTable) -> Self {
+        let co_index = co_to_index(&value.co).into();
+        let eo_index = eo_to_index(&value.eo).into();
+        let e_combo_index = e_combo_to_index(&value.ep).into();
+        let distance = table.symmetry_table.as_ref().map_or(0, |sym| {
+            sym.phase1_distance(&table.move_table, co_index, eo_index, e_combo_index)
+        });
+
+        Self {
+            co_index,
+            eo_index,
+            e_combo_index,
+            distance,
+        }
+    }
 }
 
 impl Phase for Phase1State {
@@ -31,19 +60,29 @@ impl Phase for Phase1State {
         self.co_index == 0 && self.eo_index == 0 && self.e_combo_index == 0
     }
 
-    fn next(&self, table: &MoveTable, move_index: usize) -> Self {
-        let co_index = table.co[self.co_index][move_index].into();
-        let eo_index = table.eo[self.eo_index][move_index].into();
-        let e_combo_index = table.e_combo[self.e_combo_index][move_index].into();
+    fn next(&self, table: &DataTable, move_index: usize) -> Self {
+        let move_table = &table.move_table;
+        let co_index = move_table.co[self.co_index][move_index].into();
+        let eo_index = move_table.eo[self.eo_index][move_index].into();
+        let e_combo_index = move_table.e_combo[self.e_combo_index][move_index].into();
+        let distance = table.symmetry_table.as_ref().map_or(0, |sym| {
+            sym.next_phase1_distance(self.distance, co_index, eo_index, e_combo_index)
+        });
 
         Self {
             co_index,
             eo_index,
             e_combo_index,
+            distance,
         }
     }
 
-    fn prune(&self, table: &PruningTable, depth: u8) -> bool {
+    fn prune(&self, table: &DataTable, depth: u8) -> bool {
+        if table.symmetry_table.is_some() {
+            return self.distance > depth;
+        }
+
+        let table = &table.pruning_table;
         let co_e_dist = table.co_e[self.co_index][self.e_combo_index];
         let eo_e_dist = table.eo_e[self.eo_index][self.e_combo_index];
         let max = co_e_dist.max(eo_e_dist);
@@ -52,36 +91,38 @@ impl Phase for Phase1State {
     }
 }
 
-impl From<CubieCube> for Phase1State {
-    fn from(value: CubieCube) -> Self {
-        let co_index = co_to_index(&value.co).into();
-        let eo_index = eo_to_index(&value.eo).into();
-        let e_combo_index = e_combo_to_index(&value.ep).into();
-
-        Self {
-            co_index,
-            eo_index,
-            e_combo_index,
-        }
-    }
-}
-
+#[derive(Clone, Copy)]
 struct Phase2State {
     cp_index: usize,
     ep_index: usize,
     e_ep_index: usize,
+    /// Exact distance of the corners and U-D edges, only tracked with the symmetry table.
+    distance: u8,
 }
 
-impl From<CubieCube> for Phase2State {
-    fn from(value: CubieCube) -> Self {
+impl Phase2State {
+    /// Returns the minimum number of moves needed to solve the state.
+    fn lower_bound(&self, table: &DataTable) -> u8 {
+        let table = &table.pruning_table;
+        let cp_e_dist = table.cp_e[self.cp_index][self.e_ep_index];
+        let ep_e_dist = table.ep_e[self.ep_index][self.e_ep_index];
+
+        cp_e_dist.max(ep_e_dist).max(self.distance)
+    }
+
+    fn new(value: CubieCube, table: &DataTable) -> Self {
         let cp_index = cp_to_index(&value.cp).into();
         let ep_index = ud_ep_to_index(&value.ep).into();
         let e_ep_index = e_ep_to_index(&value.ep).into();
+        let distance = table.symmetry_table.as_ref().map_or(0, |sym| {
+            sym.phase2_distance(&table.move_table, cp_index, ep_index)
+        });
 
         Self {
             cp_index,
             ep_index,
             e_ep_index,
+            distance,
         }
     }
 }
@@ -91,29 +132,31 @@ impl Phase for Phase2State {
         self.cp_index == 0 && self.ep_index == 0 && self.e_ep_index == 0
     }
 
-    fn next(&self, table: &MoveTable, move_index: usize) -> Self {
-        let cp_index = table.cp[self.cp_index][move_index].into();
-        let ep_index = table.ep[self.ep_index][move_index].into();
-        let e_ep_index = table.e_ep[self.e_ep_index][move_index].into();
+    fn next(&self, table: &DataTable, move_index: usize) -> Self {
+        let move_table = &table.move_table;
+        let cp_index = move_table.cp[self.cp_index][move_index].into();
+        let ep_index = move_table.ep[self.ep_index][move_index].into();
+        let e_ep_index = move_table.e_ep[self.e_ep_index][move_index].into();
+        let distance = table.symmetry_table.as_ref().map_or(0, |sym| {
+            sym.next_phase2_distance(self.distance, cp_index, ep_index)
+        });
 
         Self {
             cp_index,
             ep_index,
             e_ep_index,
+            distance,
         }
     }
 
-    fn prune(&self, table: &PruningTable, depth: u8) -> bool {
-        let cp_e_dist = table.cp_e[self.cp_index][self.e_ep_index];
-        let ep_e_dist = table.ep_e[self.ep_index][self.e_ep_index];
-        let max = cp_e_dist.max(ep_e_dist);
-
-        max > depth
+    fn prune(&self, table: &DataTable, depth: u8) -> bool {
+        self.lower_bound(table) > depth
     }
 }
 
 /// Two phase solution.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Solution {
     pub phase1: Vec<Move>,
     pub phase2: Vec<Move>,
@@ -127,6 +170,11 @@ impl Solution {
     pub fn is_empty(&self) -> bool {
         self.phase1.is_empty() && self.phase2.is_empty()
     }
+
+    /// Returns the number of moves in the given metric.
+    pub fn move_count(&self, metric: Metric) -> usize {
+        move_count(&self.phase1, metric) + move_count(&self.phase2, metric)
+    }
 }
 
 impl fmt::Display for Solution {
@@ -167,6 +215,103 @@ impl Solution {
     }
 }
 
+/// Solution reported by `Solver::solve_with`, each one is shorter than the previous.
+#[derive(Debug, Clone)]
+pub struct SolutionEvent {
+    pub solution: Solution,
+    /// Time since the start of the search.
+    pub elapsed: Duration,
+    /// Depth of the phase 1 search which found the solution.
+    pub depth: u8,
+}
+
+type OnSolution<'c> = dyn FnMut(&SolutionEvent) -> ControlFlow<()> + 'c;
+
+/// Search progress reported to the callback set with `Solver::set_progress`.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    /// Current depth of the phase 1 search.
+    pub depth: u8,
+    /// Number of nodes visited since the start of the search.
+    pub nodes: u64,
+    /// Length of the best solution found so far.
+    pub best_length: Option<u8>,
+    /// Time since the start of the search.
+    pub elapsed: Duration,
+}
+
+type OnProgress = dyn FnMut(&Progress) + Send;
+
+/// Number of nodes between two progress reports.
+const PROGRESS_INTERVAL: u64 = 1 << 16;
+
+/// Time between two progress reports of `Solver::solve_parallel`.
+const PARALLEL_PROGRESS_PERIOD: Duration = Duration::from_millis(100);
+
+/// Token stopping a search from another thread, clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops every search using this token, they return the best solution found so far.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Symmetries rotating the cube around the URF-DBL axis, searched by `Solver::solve_parallel`.
+const URF3_SYMMETRIES: [usize; 3] = [0, 16, 32];
+
+/// Search state shared by the threads of a parallel solve.
+struct SharedBound {
+    /// Length every new solution must be shorter than.
+    length: AtomicU8,
+    /// Set once a solution is found when no timeout asks for shorter ones.
+    done: AtomicBool,
+    /// Nodes visited by the threads, counted every `PROGRESS_INTERVAL` nodes.
+    nodes: AtomicU64,
+    /// Deepest phase 1 depth reached by the threads.
+    depth: AtomicU8,
+}
+
+/// Returns the move performed in the original orientation for a move of the conjugated state.
+fn conjugate_move(m: Move, symmetry: usize) -> Move {
+    let cube = MOVE_CUBES[m as usize].conjugate(get_inverse_symmetry(symmetry));
+
+    ALL_MOVES
+        .into_iter()
+        .find(|n| MOVE_CUBES[*n as usize] == cube)
+        .unwrap()
+}
+
+/// Maps a solution of the conjugated, possibly inverted, state back to the original state.
+/// The phases of an inverse solution are swapped.
+fn map_solution(solution: Solution, symmetry: usize, inverse: bool) -> Solution {
+    let (phase1, phase2) = match inverse {
+        true => (invert(&solution.phase2), invert(&solution.phase1)),
+        false => (solution.phase1, solution.phase2),
+    };
+    let map = |moves: Vec<Move>| {
+        moves
+            .into_iter()
+            .map(|m| conjugate_move(m, symmetry))
+            .collect()
+    };
+
+    Solution {
+        phase1: map(phase1),
+        phase2: map(phase2),
+    }
+}
+
 /// Two phase solver.
 pub struct Solver<'a> {
     data_table: &'a DataTable,
@@ -176,6 +321,13 @@ pub struct Solver<'a> {
     solution_phase1: Vec<Move>,
     solution_phase2: Vec<Move>,
     best_solution: Option<Solution>,
+    shared: Option<&'a SharedBound>,
+    stopped: bool,
+    cancellation: Option<CancellationToken>,
+    node_limit: Option<u64>,
+    nodes: u64,
+    depth: u8,
+    progress: Option<Box<OnProgress>>,
 }
 
 impl<'a> Solver<'a> {
@@ -190,75 +342,306 @@ impl<'a> Solver<'a> {
             solution_phase1: vec![],
             solution_phase2: vec![],
             best_solution: None,
+            shared: None,
+            stopped: false,
+            cancellation: None,
+            node_limit: None,
+            nodes: 0,
+            depth: 0,
+            progress: None,
+        }
+    }
+
+    /// Returns a function building a solver with the same settings on a thread of `solve_parallel`,
+    /// the progress callback stays on the calling thread.
+    fn with_shared<'s>(&self) -> impl FnOnce(&'s SharedBound) -> Solver<'s> + Send
+    where
+        'a: 's,
+    {
+        let (data_table, max_length, timeout) = (self.data_table, self.max_length, self.timeout);
+        let (cancellation, node_limit) = (self.cancellation.clone(), self.node_limit);
+
+        move |shared| Solver {
+            timeout,
+            shared: Some(shared),
+            cancellation,
+            node_limit,
+            ..Solver::new(data_table, max_length, None)
         }
     }
 
+    /// Stops the search once the token is cancelled.
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation = Some(token);
+    }
+
+    /// Stops the search after visiting the given number of nodes,
+    /// each thread of `solve_parallel` has its own budget.
+    pub fn set_node_limit(&mut self, limit: Option<u64>) {
+        self.node_limit = limit;
+    }
+
+    /// Calls `on_progress` at every phase 1 depth and periodically during the search.
+    /// `solve_parallel` calls it from the calling thread with the totals of every thread.
+    /// The callback owns its state so the solver can move to another thread,
+    /// a channel can carry the reports.
+    pub fn set_progress<F>(&mut self, on_progress: F)
+    where
+        F: FnMut(&Progress) + Send + 'static,
+    {
+        self.progress = Some(Box::new(on_progress));
+    }
+
+    /// Returns the number of nodes visited by the last search, summed over the threads of `solve_parallel`.
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    fn report_progress(&mut self, time: Instant) {
+        if let Some(on_progress) = self.progress.as_mut() {
+            on_progress(&Progress {
+                depth: self.depth,
+                nodes: self.nodes,
+                best_length: self.best_solution.as_ref().map(|s| s.len() as u8),
+                elapsed: time.elapsed(),
+            });
+        }
+    }
+
+    /// Counts a visited node, reporting progress at regular intervals.
+    fn visit(&mut self, time: Instant) {
+        self.nodes += 1;
+
+        if self.nodes.is_multiple_of(PROGRESS_INTERVAL) {
+            if let Some(shared) = self.shared {
+                shared.nodes.fetch_add(PROGRESS_INTERVAL, Ordering::Relaxed);
+            }
+
+            self.report_progress(time);
+        }
+    }
+
+    /// Returns the length solutions must be shorter than, lowered by the best solution
+    /// and by the other threads.
+    fn max_length(&self) -> u8 {
+        let best = self
+            .best_solution
+            .as_ref()
+            .map_or(self.max_length, |s| s.len() as u8);
+        let max_length = self.max_length.min(best);
+
+        self.shared.map_or(max_length, |shared| {
+            max_length.min(shared.length.load(Ordering::Relaxed))
+        })
+    }
+
+    fn should_stop(&self, time: Instant) -> bool {
+        self.stopped
+            || self.timeout.is_some_and(|timeout| time.elapsed() > timeout)
+            || self.node_limit.is_some_and(|limit| self.nodes >= limit)
+            || self
+                .cancellation
+                .as_ref()
+                .is_some_and(|token| token.is_cancelled())
+            || self
+                .shared
+                .is_some_and(|shared| shared.done.load(Ordering::Relaxed))
+    }
+
     /// Resets the solver state.
     pub fn clear(&mut self) {
         self.initial_state = CubieCube::default();
         self.solution_phase1.clear();
         self.solution_phase2.clear();
         self.best_solution.take();
+        self.stopped = false;
+        self.nodes = 0;
+        self.depth = 0;
     }
 
     /// Solves the cube using the two phase algorithm.
+    /// Without timeout the first solution is returned, otherwise the shortest one found in time.
     pub fn solve(&mut self, state: CubieCube) -> Option<Solution> {
+        let stop_at_first = self.timeout.is_none();
+
+        self.solve_with(state, |_| match stop_at_first {
+            true => ControlFlow::Break(()),
+            false => ControlFlow::Continue(()),
+        })
+    }
+
+    /// Solves the cube, calling `on_solution` with every solution shorter than the previous ones.
+    /// The search ends when the callback breaks, on timeout, cancellation or node limit,
+    /// or once no shorter solution fits in the maximum length. Returns the shortest solution found.
+    pub fn solve_with<F>(&mut self, state: CubieCube, mut on_solution: F) -> Option<Solution>
+    where
+        F: FnMut(&SolutionEvent) -> ControlFlow<()>,
+    {
+        self.clear();
         self.initial_state = state;
 
         let start = Instant::now();
 
         for depth in 0..=self.max_length {
-            let state = Phase1State::from(state);
-            let found = self.solve_phase1(state, depth, start);
+            let state = Phase1State::new(state, self.data_table);
+            self.depth = depth;
+            self.report_progress(start);
 
-            if let Some(timeout) = self.timeout {
-                if start.elapsed() > timeout {
-                    return self.best_solution.clone();
-                }
-            } else if found {
-                return self.best_solution.clone();
+            if let Some(shared) = self.shared {
+                shared.depth.fetch_max(depth, Ordering::Relaxed);
+            }
+
+            if self.solve_phase1(state, depth, start, &mut on_solution) {
+                break;
             }
         }
 
-        None
+        self.best_solution.clone()
     }
 
-    fn solve_phase1(&mut self, state: Phase1State, depth: u8, time: Instant) -> bool {
-        if let Some(timeout) = self.timeout {
-            if time.elapsed() > timeout {
-                return true;
+    /// Solves the state, its rotations around the URF-DBL axis and their inverses on separate threads,
+    /// sharing the length of the best solution. Returns the shortest solution found, in the original orientation.
+    pub fn solve_parallel(&mut self, state: CubieCube) -> Option<Solution> {
+        self.clear();
+
+        let shared = SharedBound {
+            length: AtomicU8::new(self.max_length),
+            done: AtomicBool::new(false),
+            nodes: AtomicU64::new(0),
+            depth: AtomicU8::new(0),
+        };
+        let start = Instant::now();
+        let caller = thread::current();
+
+        let results: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = URF3_SYMMETRIES
+                .into_iter()
+                .flat_map(|symmetry| [(symmetry, false), (symmetry, true)])
+                .map(|(symmetry, inverse)| {
+                    let build = self.with_shared();
+                    let (shared, caller) = (&shared, caller.clone());
+
+                    scope.spawn(move || {
+                        let mut solver = build(shared);
+                        let conjugated = state.conjugate(symmetry);
+                        let target = match inverse {
+                            true => conjugated.inverse(),
+                            false => conjugated,
+                        };
+                        let solution = solver
+                            .solve(target)
+                            .map(|solution| map_solution(solution, symmetry, inverse));
+
+                        caller.unpark();
+                        (solution, solver.nodes())
+                    })
+                })
+                .collect();
+
+            while !handles.iter().all(|h| h.is_finished()) {
+                thread::park_timeout(PARALLEL_PROGRESS_PERIOD);
+                self.nodes = shared.nodes.load(Ordering::Relaxed);
+                self.depth = shared.depth.load(Ordering::Relaxed);
+                self.report_shared_progress(&shared, start);
             }
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        self.initial_state = state;
+        self.nodes = results.iter().map(|(_, nodes)| nodes).sum();
+        self.best_solution = results
+            .into_iter()
+            .filter_map(|(solution, _)| solution)
+            .min_by_key(|s| s.len());
+        self.report_shared_progress(&shared, start);
+        self.best_solution.clone()
+    }
+
+    /// Reports the progress of the threads of `solve_parallel`, solutions are shorter
+    /// than the maximum length so the shared bound is lowered once one is found.
+    fn report_shared_progress(&mut self, shared: &SharedBound, time: Instant) {
+        if let Some(on_progress) = self.progress.as_mut() {
+            let length = shared.length.load(Ordering::Relaxed);
+
+            on_progress(&Progress {
+                depth: self.depth,
+                nodes: self.nodes,
+                best_length: (length < self.max_length).then_some(length),
+                elapsed: time.elapsed(),
+            });
+        }
+    }
+
+    /// Solves a cube with oriented centers: the two phase solution is followed by
+    /// the moves turning back the centers. Returns `None` for unsolvable states.
+    pub fn solve_supercube(&mut self, cube: SuperCube) -> Option<Vec<Move>> {
+        if !cube.is_solvable() {
+            return None;
+        }
+
+        let mut moves = self.solve(cube.state)?.get_all_moves();
+        let centers = cube.apply_moves(&moves).centers;
+        moves.extend(get_center_fix(centers)?);
+
+        Some(simplify(&moves))
+    }
+
+    /// Returns `true` when the search must stop.
+    fn solve_phase1(
+        &mut self,
+        state: Phase1State,
+        depth: u8,
+        time: Instant,
+        on_solution: &mut OnSolution,
+    ) -> bool {
+        if self.should_stop(time) {
+            return true;
         }
 
+        self.visit(time);
+
         if depth == 0 && state.is_solved() {
+            // a phase 2 move ending phase 1 was already tried as the start of phase 2
+            if self
+                .solution_phase1
+                .last()
+                .is_some_and(|m| PHASE2_MOVES.contains(m))
+            {
+                return false;
+            }
+
             let mut cube_state = self.initial_state;
 
             for m in &self.solution_phase1 {
                 cube_state = cube_state.apply_move(*m);
             }
 
+            let max_length = self.max_length();
             let max_depth = match self.solution_phase1.len() {
-                0 => self.max_length,
+                0 => max_length,
                 _ => {
-                    if self.max_length > self.solution_phase1.len() as u8 {
-                        self.max_length - self.solution_phase1.len() as u8
+                    if max_length > self.solution_phase1.len() as u8 {
+                        max_length - self.solution_phase1.len() as u8
                     } else {
                         return true;
                     }
                 }
             };
 
-            for phase2_depth in 0..max_depth {
-                let state = Phase2State::from(cube_state);
-                if self.solve_phase2(state, phase2_depth, time) {
-                    return true;
+            let state = Phase2State::new(cube_state, self.data_table);
+
+            for phase2_depth in state.lower_bound(self.data_table)..max_depth {
+                if self.solve_phase2(state, phase2_depth, time, on_solution) {
+                    // keep searching for shorter solutions unless stopped
+                    return self.should_stop(time);
                 }
             }
 
             return false;
         }
 
-        if state.prune(&self.data_table.pruning_table, depth) || depth == 0 {
+        if state.prune(self.data_table, depth) || depth == 0 {
             return false;
         }
 
@@ -271,8 +654,8 @@ impl<'a> Solver<'a> {
 
             self.solution_phase1.push(*m);
 
-            let new_state = state.next(&self.data_table.move_table, i);
-            let found = self.solve_phase1(new_state, depth - 1, time);
+            let new_state = state.next(self.data_table, i);
+            let found = self.solve_phase1(new_state, depth - 1, time, on_solution);
 
             if found {
                 return true;
@@ -284,32 +667,56 @@ impl<'a> Solver<'a> {
         false
     }
 
-    fn solve_phase2(&mut self, state: Phase2State, depth: u8, time: Instant) -> bool {
-        if let Some(timeout) = self.timeout {
-            if time.elapsed() > timeout {
-                return true;
-            }
+    /// Returns `true` when a solution is found or the search must stop.
+    fn solve_phase2(
+        &mut self,
+        state: Phase2State,
+        depth: u8,
+        time: Instant,
+        on_solution: &mut OnSolution,
+    ) -> bool {
+        if self.should_stop(time) {
+            return true;
         }
 
+        self.visit(time);
+
         if depth == 0 && state.is_solved() {
             let solution = Solution {
                 phase1: self.solution_phase1.clone(),
                 phase2: self.solution_phase2.clone(),
             };
 
-            if let Some(best_solution) = &mut self.best_solution {
-                let current_length = self.solution_phase1.len() + self.solution_phase2.len();
-                if best_solution.len() > current_length {
-                    *best_solution = solution
+            if self
+                .best_solution
+                .as_ref()
+                .is_some_and(|best| best.len() <= solution.len())
+            {
+                return true;
+            }
+
+            let length = solution.len() as u8;
+            let event = SolutionEvent {
+                solution: solution.clone(),
+                elapsed: time.elapsed(),
+                depth: self.solution_phase1.len() as u8,
+            };
+
+            self.best_solution = Some(solution);
+            self.stopped = on_solution(&event).is_break();
+
+            if let Some(shared) = self.shared {
+                shared.length.fetch_min(length, Ordering::Relaxed);
+
+                if self.stopped {
+                    shared.done.store(true, Ordering::Relaxed);
                 }
-            } else {
-                self.best_solution = Some(solution)
             }
 
             return true;
         }
 
-        if state.prune(&self.data_table.pruning_table, depth) || depth == 0 {
+        if state.prune(self.data_table, depth) || depth == 0 {
             return false;
         }
 
@@ -326,8 +733,8 @@ impl<'a> Solver<'a> {
 
             self.solution_phase2.push(*m);
 
-            let new_state = state.next(&self.data_table.move_table, i);
-            let found = self.solve_phase2(new_state, depth - 1, time);
+            let new_state = state.next(self.data_table, i);
+            let found = self.solve_phase2(new_state, depth - 1, time, on_solution);
 
             if found {
                 return true;
@@ -343,7 +750,8 @@ impl<'a> Solver<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{cube::cubie::SOLVED_CUBIE_CUBE, Move::*};
+    use crate::{cube::cubie::SOLVED_CUBIE_CUBE, generators::generate_random_state, Move::*};
+    use std::sync::mpsc;
 
     #[test]
     fn test_solve() {
@@ -358,4 +766,221 @@ mod test {
 
         assert_eq!(solved_state, SOLVED_CUBIE_CUBE);
     }
+
+    #[test]
+    fn test_solve_supercube() {
+        let scramble = vec![R, U2, F3, L, D, B2, U3, R3, F];
+        let cube = SuperCube::from(&scramble);
+        let table = DataTable::default();
+        let mut solver = Solver::new(&table, 23, None);
+        let solution = solver.solve_supercube(cube).unwrap();
+
+        assert!(cube.apply_moves(&solution).is_solved());
+
+        let mut twisted = cube;
+        twisted.centers[0] = (twisted.centers[0] + 1) % 4;
+        assert!(solver.solve_supercube(twisted).is_none());
+    }
+
+    #[test]
+    fn test_solve_parallel() {
+        let state = CubieCube::from(&vec![R, U2, F3, L, D, B2, U3, R3, F, D2]);
+        let table = DataTable::default();
+        let mut solver = Solver::new(&table, 23, None);
+
+        for symmetry in URF3_SYMMETRIES {
+            for inverse in [false, true] {
+                let conjugated = state.conjugate(symmetry);
+                let target = match inverse {
+                    true => conjugated.inverse(),
+                    false => conjugated,
+                };
+                let solution = map_solution(solver.solve(target).unwrap(), symmetry, inverse);
+
+                assert!(state.apply_moves(&solution.get_all_moves()).is_solved());
+                solver.clear();
+            }
+        }
+
+        let solution = solver.solve_parallel(state).unwrap();
+        assert!(state.apply_moves(&solution.get_all_moves()).is_solved());
+        assert!(solution.len() < 23);
+    }
+
+    #[test]
+    fn test_solve_with() {
+        let scramble = vec![
+            D3, R2, L3, U2, F, R, F3, D2, R2, F2, B2, U2, R2, F2, U, R2, U3, R2, D2,
+        ];
+        let state = CubieCube::from(&scramble);
+        let table = DataTable::default();
+        let mut solver = Solver::new(&table, 23, Some(1.0));
+        let mut events = vec![];
+        let solution = solver.solve_with(state, |event| {
+            events.push(event.clone());
+            ControlFlow::Continue(())
+        });
+
+        assert!(!events.is_empty());
+        assert!(events
+            .windows(2)
+            .all(|w| w[0].solution.len() > w[1].solution.len()));
+        assert_eq!(
+            solution.unwrap().len(),
+            events.last().unwrap().solution.len()
+        );
+
+        let mut count = 0;
+        let solution = solver.solve_with(state, |_| {
+            count += 1;
+            ControlFlow::Break(())
+        });
+
+        assert_eq!(count, 1);
+        assert!(state
+            .apply_moves(&solution.unwrap().get_all_moves())
+            .is_solved());
+    }
+
+    #[test]
+    fn test_phase2_search() {
+        let table = DataTable::default();
+        let mut solver = Solver::new(&table, 23, None);
+
+        // a state of the phase 2 subgroup starts phase 2 at its lower bound
+        let state = CubieCube::from(&vec![U, R2, D3, F2]);
+        let solution = solver.solve(state).unwrap();
+
+        assert!(solution.phase1.is_empty());
+        assert_eq!(solution.phase2.len(), 4);
+        assert!(state.apply_moves(&solution.get_all_moves()).is_solved());
+
+        // phase 1 never ends with a phase 2 move, it is tried as the start of phase 2
+        for _ in 0..5 {
+            let state = generate_random_state();
+            let solution = solver.solve(state).unwrap();
+
+            assert!(!solution
+                .phase1
+                .last()
+                .is_some_and(|m| PHASE2_MOVES.contains(m)));
+            assert!(state.apply_moves(&solution.get_all_moves()).is_solved());
+        }
+    }
+
+    #[test]
+    fn test_cancellation_and_progress() {
+        let state = CubieCube::from(&vec![R, U2, F3, L, D, B2, U3, R3, F, D2]);
+        let table = DataTable::default();
+        let mut solver = Solver::new(&table, 23, None);
+        let token = CancellationToken::new();
+
+        token.cancel();
+        solver.set_cancellation_token(token);
+        assert!(solver.solve(state).is_none());
+        assert!(solver.solve_parallel(state).is_none());
+
+        // the node limit returns the best solution found so far, none if it stops before the first one
+        let mut solver = Solver::new(&table, 18, Some(10.0));
+        solver.set_node_limit(Some(1000));
+        assert!(solver.solve(state).is_none());
+        assert!(solver.nodes() <= 1000);
+
+        let mut solver = Solver::new(&table, 23, Some(10.0));
+        let mut found = vec![];
+        solver.set_node_limit(Some(200_000));
+        let solution = solver.solve_with(state, |event| {
+            found.push(event.solution.clone());
+            ControlFlow::Continue(())
+        });
+
+        assert!(solver.nodes() <= 200_000);
+        assert_eq!(
+            solution.as_ref().map(|s| s.get_all_moves()),
+            found.last().map(|s| s.get_all_moves())
+        );
+        assert!(state
+            .apply_moves(&solution.unwrap().get_all_moves())
+            .is_solved());
+
+        let (sender, receiver) = mpsc::channel();
+        let mut solver = Solver::new(&table, 23, None);
+        solver.set_progress(move |progress| sender.send(progress.clone()).unwrap());
+        let solution = solver.solve(state).unwrap();
+        let reports: Vec<_> = receiver.try_iter().collect();
+
+        assert!(state.apply_moves(&solution.get_all_moves()).is_solved());
+        assert_eq!(reports[0].depth, 0);
+        assert!(reports.windows(2).all(|w| w[0].nodes <= w[1].nodes));
+    }
+
+    #[test]
+    fn test_solver_is_send() {
+        fn assert_send<T: Send>() {}
+
+        assert_send::<Solver<'static>>();
+    }
+
+    #[test]
+    fn test_cancel_from_thread() {
+        // no solution of the superflip is shorter than 20 moves, only the token stops the search
+        let superflip = CubieCube {
+            eo: [1; 12],
+            ..CubieCube::default()
+        };
+        let table = DataTable::default();
+
+        for parallel in [false, true] {
+            let token = CancellationToken::new();
+            let mut solver = Solver::new(&table, 20, None);
+            solver.set_cancellation_token(token.clone());
+
+            let handle = thread::spawn(move || {
+                thread::sleep(Duration::from_millis(200));
+                token.cancel();
+            });
+            let solution = match parallel {
+                true => solver.solve_parallel(superflip),
+                false => solver.solve(superflip),
+            };
+
+            handle.join().unwrap();
+            assert!(solution.is_none());
+            assert!(solver.nodes() > 0);
+        }
+    }
+
+    #[test]
+    fn test_parallel_progress() {
+        let state = CubieCube::from(&vec![R, U2, F3, L, D, B2, U3, R3, F, D2]);
+        let table = DataTable::default();
+        let (sender, receiver) = mpsc::channel();
+        let mut solver = Solver::new(&table, 23, None);
+        solver.set_progress(move |progress| sender.send(progress.clone()).unwrap());
+        let solution = solver.solve_parallel(state).unwrap();
+        let nodes = solver.nodes();
+        let reports: Vec<_> = receiver.try_iter().collect();
+        let last = reports.last().unwrap();
+
+        assert!(nodes > 0);
+        assert_eq!(last.nodes, nodes);
+        assert_eq!(last.best_length, Some(solution.len() as u8));
+        assert!(reports.windows(2).all(|w| w[0].nodes <= w[1].nodes));
+    }
+
+    #[test]
+    #[ignore = "builds the full symmetry tables"]
+    fn test_solve_with_symmetry() {
+        let table = DataTable::with_symmetry();
+        let mut solver = Solver::new(&table, 21, None);
+
+        for _ in 0..20 {
+            let state = generate_random_state();
+            let solution = solver.solve(state).unwrap();
+
+            assert!(solution.len() <= 20);
+            assert!(state.apply_moves(&solution.get_all_moves()).is_solved());
+            solver.clear();
+        }
+    }
 }
@@ -0,0 +1,459 @@
+use super::{
+    moves::*,
+    utils::{Table, ALL_MOVES, PHASE2_MOVES},
+};
+use crate::cube::{constants::*, cubie::CubieCube, index::*, symmetry::get_inverse_symmetry};
+use bincode::{Decode, Encode};
+
+/// The count of symmetries preserving the U-D axis, the first ones of `symmetry::get_symmetry`.
+pub const UD_SYM_COUNT: usize = 16;
+/// The total count of flip-slice coordinates, `2048 * slice + flip`.
+pub const FLIPSLICE_COUNT: usize = EO_COUNT as usize * E_COMBO_COUNT as usize;
+/// The total count of flip-slice symmetry classes.
+pub const FLIPSLICE_CLASS_COUNT: usize = 64430;
+/// The total count of corner permutation symmetry classes.
+pub const CORNER_CLASS_COUNT: usize = 2768;
+
+/// Value of the entries which are not filled yet.
+const EMPTY: u8 = 3;
+
+/// Symmetry classes of a coordinate under the 16 U-D symmetries.
+#[derive(Encode, Decode)]
+pub struct SymCoordinate {
+    /// Class of each coordinate.
+    pub class: Vec<u16>,
+    /// Symmetry conjugating each coordinate to the representative of its class.
+    pub sym: Vec<u8>,
+    /// Smallest coordinate of each class.
+    pub rep: Vec<u32>,
+}
+
+impl SymCoordinate {
+    /// Classifies the coordinates, `conjugate` returns the coordinate conjugated by a symmetry.
+    fn new(count: usize, conjugate: impl Fn(usize, usize) -> usize) -> Self {
+        let mut class = vec![u16::MAX; count];
+        let mut sym = vec![0; count];
+        let mut rep = Vec::new();
+
+        for i in 0..count {
+            if class[i] != u16::MAX {
+                continue;
+            }
+
+            for s in 0..UD_SYM_COUNT {
+                let j = conjugate(i, s);
+
+                if class[j] == u16::MAX {
+                    class[j] = rep.len() as u16;
+                    sym[j] = get_inverse_symmetry(s) as u8;
+                }
+            }
+
+            rep.push(i as u32);
+        }
+
+        Self { class, sym, rep }
+    }
+
+    /// Returns the symmetries leaving the representative of each class unchanged, as bit masks.
+    fn self_symmetries(&self, conjugate: impl Fn(usize, usize) -> usize) -> Vec<u16> {
+        self.rep
+            .iter()
+            .map(|r| {
+                (1..UD_SYM_COUNT)
+                    .filter(|s| conjugate(*r as usize, *s) == *r as usize)
+                    .fold(0, |acc, s| acc | 1 << s)
+            })
+            .collect()
+    }
+}
+
+/// Symmetry reduced pruning tables, distances are stored modulo 3 using 2 bits per entry.
+#[derive(Encode, Decode)]
+pub struct SymmetryTable {
+    pub flipslice: SymCoordinate,
+    pub corners: SymCoordinate,
+    /// Corner orientation conjugated by each symmetry.
+    pub co_conj: Table<u16>,
+    /// U-D edge permutation conjugated by each symmetry.
+    pub ud_ep_conj: Table<u16>,
+    /// Phase 1 distances indexed by flip-slice class and corner orientation.
+    pub co_flipslice: Vec<u32>,
+    /// Phase 2 distances indexed by corner permutation class and U-D edge permutation.
+    pub cp_ud_ep: Vec<u32>,
+}
+
+impl Default for SymmetryTable {
+    fn default() -> Self {
+        Self::new(&MoveTable::default())
+    }
+}
+
+fn get_depth3(table: &[u32], index: usize) -> u8 {
+    (table[index / 16] >> (2 * (index % 16)) & 3) as u8
+}
+
+fn set_depth3(table: &mut [u32], index: usize, value: u8) {
+    let shift = 2 * (index % 16);
+    table[index / 16] = table[index / 16] & !(3 << shift) | (value as u32) << shift;
+}
+
+/// Returns the distance of a neighbour from its distance modulo 3.
+fn next_distance(distance: u8, depth3: u8) -> u8 {
+    match (depth3 + 3 - distance % 3) % 3 {
+        0 => distance,
+        1 => distance + 1,
+        _ => distance - 1,
+    }
+}
+
+fn conjugate_flipslice(index: usize, s: usize) -> usize {
+    let state = CubieCube {
+        ep: index_to_e_combo((index / EO_COUNT as usize) as u16),
+        eo: index_to_eo((index % EO_COUNT as usize) as u16),
+        ..Default::default()
+    }
+    .conjugate(s);
+
+    e_combo_to_index(&state.ep) as usize * EO_COUNT as usize + eo_to_index(&state.eo) as usize
+}
+
+fn conjugate_cp(index: usize, s: usize) -> usize {
+    let state = CubieCube {
+        cp: index_to_cp(index as u16),
+        ..Default::default()
+    }
+    .conjugate(s);
+
+    cp_to_index(&state.cp) as usize
+}
+
+fn conjugate_co(index: usize, s: usize) -> usize {
+    let state = CubieCube {
+        co: index_to_co(index as u16),
+        ..Default::default()
+    }
+    .conjugate(s);
+
+    co_to_index(&state.co) as usize
+}
+
+fn conjugate_ud_ep(index: usize, s: usize) -> usize {
+    let state = CubieCube {
+        ep: index_to_ud_ep(index as u16),
+        ..Default::default()
+    }
+    .conjugate(s);
+
+    ud_ep_to_index(&state.ep) as usize
+}
+
+fn get_conj_table(count: usize, conjugate: impl Fn(usize, usize) -> usize) -> Table<u16> {
+    (0..count)
+        .map(|i| (0..UD_SYM_COUNT).map(|s| conjugate(i, s) as u16).collect())
+        .collect()
+}
+
+/// Breadth first search over the pairs (class, coordinate), `neighbour` returns the index
+/// reached by a move and `equivalents` the indices sharing the distance of an index.
+fn get_sym_prune_table(
+    size: usize,
+    moves: usize,
+    neighbour: impl Fn(usize, usize) -> usize,
+    equivalents: impl Fn(usize) -> Vec<usize>,
+) -> Vec<u32> {
+    let mut table = vec![u32::MAX; size.div_ceil(16)];
+    let mut depth: u8 = 0;
+    let mut filled = 1;
+
+    set_depth3(&mut table, 0, 0);
+
+    while filled != size {
+        let last_filled = filled;
+        // searching from the empty entries is faster once most of the table is filled
+        let backward = filled > size / 2;
+        let (current, next) = (depth % 3, (depth + 1) % 3);
+
+        for i in 0..size {
+            let value = get_depth3(&table, i);
+
+            if backward {
+                if value == EMPTY
+                    && (0..moves).any(|m| get_depth3(&table, neighbour(i, m)) == current)
+                {
+                    set_depth3(&mut table, i, next);
+                    filled += 1;
+                }
+            } else if value == current {
+                for m in 0..moves {
+                    let j = neighbour(i, m);
+
+                    if get_depth3(&table, j) != EMPTY {
+                        continue;
+                    }
+
+                    for k in equivalents(j) {
+                        if get_depth3(&table, k) == EMPTY {
+                            set_depth3(&mut table, k, next);
+                            filled += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        if filled == last_filled {
+            break;
+        }
+
+        depth += 1;
+    }
+
+    table
+}
+
+impl SymmetryTable {
+    /// Builds the tables from the move tables, this takes a while.
+    pub fn new(move_table: &MoveTable) -> Self {
+        let flipslice = SymCoordinate::new(FLIPSLICE_COUNT, conjugate_flipslice);
+        let corners = SymCoordinate::new(CP_COUNT as usize, conjugate_cp);
+        let co_conj = get_conj_table(CO_COUNT as usize, conjugate_co);
+        let ud_ep_conj = get_conj_table(UD_EP_COUNT as usize, conjugate_ud_ep);
+        let mut table = Self {
+            flipslice,
+            corners,
+            co_conj,
+            ud_ep_conj,
+            co_flipslice: vec![],
+            cp_ud_ep: vec![],
+        };
+
+        let co_count = CO_COUNT as usize;
+        let self_syms = table.flipslice.self_symmetries(conjugate_flipslice);
+        let co_flipslice = get_sym_prune_table(
+            FLIPSLICE_CLASS_COUNT * co_count,
+            ALL_MOVES.len(),
+            |i, m| {
+                let flipslice = table.flipslice.rep[i / co_count] as usize;
+                let (e_combo, eo) = (flipslice / EO_COUNT as usize, flipslice % EO_COUNT as usize);
+
+                table.phase1_index(
+                    move_table.co[i % co_count][m].into(),
+                    move_table.eo[eo][m].into(),
+                    move_table.e_combo[e_combo][m].into(),
+                )
+            },
+            |i| equivalent_indices(i, co_count, self_syms[i / co_count], &table.co_conj),
+        );
+        table.co_flipslice = co_flipslice;
+
+        let ep_count = UD_EP_COUNT as usize;
+        let self_syms = table.corners.self_symmetries(conjugate_cp);
+        let cp_ud_ep = get_sym_prune_table(
+            CORNER_CLASS_COUNT * ep_count,
+            PHASE2_MOVES.len(),
+            |i, m| {
+                let cp = table.corners.rep[i / ep_count] as usize;
+
+                table.phase2_index(
+                    move_table.cp[cp][m].into(),
+                    move_table.ep[i % ep_count][m].into(),
+                )
+            },
+            |i| equivalent_indices(i, ep_count, self_syms[i / ep_count], &table.ud_ep_conj),
+        );
+        table.cp_ud_ep = cp_ud_ep;
+
+        table
+    }
+
+    fn phase1_index(&self, co: usize, eo: usize, e_combo: usize) -> usize {
+        let flipslice = e_combo * EO_COUNT as usize + eo;
+        let class = self.flipslice.class[flipslice] as usize;
+        let sym = self.flipslice.sym[flipslice] as usize;
+
+        class * CO_COUNT as usize + self.co_conj[co][sym] as usize
+    }
+
+    fn phase2_index(&self, cp: usize, ud_ep: usize) -> usize {
+        let class = self.corners.class[cp] as usize;
+        let sym = self.corners.sym[cp] as usize;
+
+        class * UD_EP_COUNT as usize + self.ud_ep_conj[ud_ep][sym] as usize
+    }
+
+    /// Returns the phase 1 distance modulo 3.
+    pub fn phase1_depth3(&self, co: usize, eo: usize, e_combo: usize) -> u8 {
+        get_depth3(&self.co_flipslice, self.phase1_index(co, eo, e_combo))
+    }
+
+    /// Returns the phase 2 distance of the corners and U-D edges modulo 3.
+    pub fn phase2_depth3(&self, cp: usize, ud_ep: usize) -> u8 {
+        get_depth3(&self.cp_ud_ep, self.phase2_index(cp, ud_ep))
+    }
+
+    /// Returns the distance of a phase 1 neighbour, knowing the distance of the current state.
+    pub fn next_phase1_distance(&self, distance: u8, co: usize, eo: usize, e_combo: usize) -> u8 {
+        next_distance(distance, self.phase1_depth3(co, eo, e_combo))
+    }
+
+    /// Returns the distance of a phase 2 neighbour, knowing the distance of the current state.
+    pub fn next_phase2_distance(&self, distance: u8, cp: usize, ud_ep: usize) -> u8 {
+        next_distance(distance, self.phase2_depth3(cp, ud_ep))
+    }
+
+    /// Returns the exact number of moves needed to solve phase 1.
+    pub fn phase1_distance(&self, table: &MoveTable, co: usize, eo: usize, e_combo: usize) -> u8 {
+        let (mut co, mut eo, mut e_combo) = (co, eo, e_combo);
+        let mut depth3 = self.phase1_depth3(co, eo, e_combo);
+        let mut distance = 0;
+
+        while co != 0 || eo != 0 || e_combo != 0 {
+            // one of the moves always gets closer to the solved state
+            (co, eo, e_combo) = (0..ALL_MOVES.len())
+                .map(|m| {
+                    (
+                        table.co[co][m].into(),
+                        table.eo[eo][m].into(),
+                        table.e_combo[e_combo][m].into(),
+                    )
+                })
+                .find(|(c, o, e)| self.phase1_depth3(*c, *o, *e) == (depth3 + 2) % 3)
+                .unwrap();
+            depth3 = (depth3 + 2) % 3;
+            distance += 1;
+        }
+
+        distance
+    }
+
+    /// Returns the exact number of moves needed to solve the corners and U-D edges in phase 2.
+    pub fn phase2_distance(&self, table: &MoveTable, cp: usize, ud_ep: usize) -> u8 {
+        let (mut cp, mut ud_ep) = (cp, ud_ep);
+        let mut depth3 = self.phase2_depth3(cp, ud_ep);
+        let mut distance = 0;
+
+        while cp != 0 || ud_ep != 0 {
+            (cp, ud_ep) = (0..PHASE2_MOVES.len())
+                .map(|m| (table.cp[cp][m].into(), table.ep[ud_ep][m].into()))
+                .find(|(c, e)| self.phase2_depth3(*c, *e) == (depth3 + 2) % 3)
+                .unwrap();
+            depth3 = (depth3 + 2) % 3;
+            distance += 1;
+        }
+
+        distance
+    }
+}
+
+/// Returns the index and the ones of the same class conjugated by its self symmetries.
+fn equivalent_indices(index: usize, count: usize, self_syms: u16, conj: &Table<u16>) -> Vec<usize> {
+    let (class, coord) = (index / count, index % count);
+    let mut indices = vec![index];
+
+    indices.extend(
+        (1..UD_SYM_COUNT)
+            .filter(|s| self_syms & 1 << s != 0)
+            .map(|s| class * count + conj[coord][s] as usize),
+    );
+
+    indices
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{generators::generate_random_state, Move::*};
+
+    #[test]
+    fn test_sym_coordinates() {
+        let flipslice = SymCoordinate::new(FLIPSLICE_COUNT, conjugate_flipslice);
+        let corners = SymCoordinate::new(CP_COUNT as usize, conjugate_cp);
+
+        assert_eq!(flipslice.rep.len(), FLIPSLICE_CLASS_COUNT);
+        assert_eq!(corners.rep.len(), CORNER_CLASS_COUNT);
+
+        let state = generate_random_state();
+
+        for s in 0..UD_SYM_COUNT {
+            let conj = state.conjugate(s);
+            let flipslice =
+                e_combo_to_index(&state.ep) as usize * 2048 + eo_to_index(&state.eo) as usize;
+
+            assert_eq!(
+                conjugate_flipslice(flipslice, s),
+                e_combo_to_index(&conj.ep) as usize * 2048 + eo_to_index(&conj.eo) as usize
+            );
+            assert_eq!(
+                conjugate_co(co_to_index(&state.co) as usize, s),
+                co_to_index(&conj.co) as usize
+            );
+            assert_eq!(
+                conjugate_cp(cp_to_index(&state.cp) as usize, s),
+                cp_to_index(&conj.cp) as usize
+            );
+        }
+
+        let phase2 = CubieCube::from(&vec![U, R2, D3, F2, L2, U2, B2]);
+
+        for s in 0..UD_SYM_COUNT {
+            assert_eq!(
+                conjugate_ud_ep(ud_ep_to_index(&phase2.ep) as usize, s),
+                ud_ep_to_index(&phase2.conjugate(s).ep) as usize
+            );
+        }
+    }
+
+    #[test]
+    fn test_depth3() {
+        let mut table = vec![u32::MAX; 2];
+
+        set_depth3(&mut table, 17, 1);
+        set_depth3(&mut table, 3, 2);
+
+        assert_eq!(get_depth3(&table, 17), 1);
+        assert_eq!(get_depth3(&table, 3), 2);
+        assert_eq!(get_depth3(&table, 16), EMPTY);
+        assert_eq!(next_distance(5, 0), 6);
+        assert_eq!(next_distance(5, 1), 4);
+        assert_eq!(next_distance(5, 2), 5);
+    }
+
+    #[test]
+    #[ignore = "builds the full symmetry tables"]
+    fn test_symmetry_table() {
+        let move_table = MoveTable::default();
+        let table = SymmetryTable::new(&move_table);
+        let state = CubieCube::from(&vec![R, U, F, D2, L3, B]);
+        let one_move = CubieCube::from(&vec![F]);
+        let (co, eo, e_combo) = (
+            co_to_index(&state.co) as usize,
+            eo_to_index(&state.eo).into(),
+            e_combo_to_index(&state.ep).into(),
+        );
+
+        assert_eq!(table.phase1_distance(&move_table, 0, 0, 0), 0);
+        assert_eq!(
+            table.phase1_distance(
+                &move_table,
+                co_to_index(&one_move.co).into(),
+                eo_to_index(&one_move.eo).into(),
+                e_combo_to_index(&one_move.ep).into()
+            ),
+            1
+        );
+        assert!(table.phase1_distance(&move_table, co, eo, e_combo) <= 6);
+        assert!((0..CORNER_CLASS_COUNT * UD_EP_COUNT as usize)
+            .all(|i| get_depth3(&table.cp_ud_ep, i) != EMPTY));
+
+        let phase2 = CubieCube::from(&vec![U, R2, D3]);
+        assert_eq!(
+            table.phase2_distance(
+                &move_table,
+                cp_to_index(&phase2.cp).into(),
+                ud_ep_to_index(&phase2.ep) as usize
+            ),
+            3
+        );
+    }
+}
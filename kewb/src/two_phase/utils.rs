@@ -2,20 +2,59 @@ use crate::{
     cube::moves::Move::{self, *},
     move_table::MoveTable,
     pruning_table::PruningTable,
+    symmetry_table::SymmetryTable,
 };
 
 use bincode::{Decode, Encode};
 
 pub const ALL_MOVES: [Move; 18] = [
-    U, U2, U3, D, D2, D3, R, R2, R3, L, L2, L3, F, F2, F2, B, B3, B2,
+    U, U2, U3, D, D2, D3, R, R2, R3, L, L2, L3, F, F2, F3, B, B2, B3,
 ];
 pub const PHASE2_MOVES: [Move; 10] = [U, U2, U3, D, D2, D3, R2, L2, F2, B2];
 
 pub type Table<T> = Vec<Vec<T>>;
 
+/// Integer type storing coordinate values in a move table.
+pub trait TableIndex: Copy {
+    fn from_index(index: usize) -> Self;
+    fn to_index(self) -> usize;
+}
+
+macro_rules! impl_table_index {
+    ($($t:ty),*) => {
+        $(impl TableIndex for $t {
+            fn from_index(index: usize) -> Self {
+                <$t>::try_from(index).expect("coordinate does not fit the table type")
+            }
+
+            fn to_index(self) -> usize {
+                self as usize
+            }
+        })*
+    };
+}
+
+impl_table_index!(u8, u16, u32);
+
 /// Contains the move and prunning table used by the two-phase algorithm
 #[derive(Default, Encode, Decode)]
 pub struct DataTable {
     pub move_table: MoveTable,
     pub pruning_table: PruningTable,
+    /// Optional symmetry reduced pruning tables, much stronger but slow to generate.
+    pub symmetry_table: Option<SymmetryTable>,
+}
+
+impl DataTable {
+    /// Generates the tables including the symmetry reduced pruning tables.
+    pub fn with_symmetry() -> Self {
+        let move_table = MoveTable::default();
+        let symmetry_table = SymmetryTable::new(&move_table);
+
+        Self {
+            move_table,
+            pruning_table: PruningTable::default(),
+            symmetry_table: Some(symmetry_table),
+        }
+    }
 }